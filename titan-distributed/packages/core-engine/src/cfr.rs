@@ -0,0 +1,519 @@
+//! Titan Core Engine — Counterfactual Regret Minimization (CFR) Solver
+//!
+//! Replaces `solver::compute_strategy`'s fixed equity-threshold heuristic
+//! with an actual equilibrium solve over a small postflop betting tree, via
+//! vanilla CFR (regret matching) and a chance-sampling variant (CFR-CS).
+//!
+//! ## Scope
+//!
+//! The full Omaha game tree — every street, every board runout, hand
+//! isomorphism — is the neural-net "Deep CFR" lookup described as future
+//! work at the top of `solver.rs`. This module solves a scoped-down but
+//! real two-player betting tree for hero's *current* decision: one opening
+//! action (check / bet / all-in), at most one raise per side, capped at
+//! all-in. That covers every `SolveResult` frequency slot
+//! (fold/check/call/raise/allin) while staying small enough to run to
+//! convergence in milliseconds per hand — the same latency budget
+//! `solve_state` already works under.
+//!
+//! ## Algorithm
+//!
+//! Each decision node is an information set keyed by `(NodeId, bucket)`,
+//! where `bucket` is hero's precomputed equity bucketed into deciles (a
+//! stand-in for a full hand-isomorphism abstraction — there's only one real
+//! hero hand per `solve()` call, so only one bucket is ever populated, but
+//! the key shape is the real one a multi-hand abstraction would use). Two
+//! tables are kept per info set, indexed by the node's legal actions:
+//! - `regret_sum[a]`: cumulative counterfactual regret for not having
+//!   always played `a`.
+//! - `strategy_sum[a]`: cumulative reach-weighted strategy, whose
+//!   normalized average is the CFR output.
+//!
+//! Each iteration: the current strategy is regret matching —
+//! `σ[a] = max(R[a], 0) / Σ_b max(R[b], 0)`, or uniform if every regret is
+//! ≤ 0 — and traversal computes `v(a)` for each action recursively, then
+//! `v = Σ_a σ[a]·v(a)`. Regret and strategy sums update from each player's
+//! own perspective: `R[a] += reach_opp·(v(a) − v)`,
+//! `S[a] += reach_self·σ[a]` (flipping sign on `v` for villain's node, since
+//! the payoffs below are hero-relative in a zero-sum game). After
+//! `iterations` rounds the returned strategy at the root is the normalized
+//! average `S[a] / Σ_b S[b]`.
+//!
+//! Showdown terminals get their payoff from [`omaha::monte_carlo_equity`]
+//! (vanilla — one generous-sims estimate, reused every iteration) or
+//! [`omaha::monte_carlo_equity_seeded`] with `sims = 1` (CFR-CS — a fresh
+//! single sampled runout every iteration, i.e. chance sampling instead of
+//! enumerating/averaging over all chance outcomes up front). `solve`'s
+//! caller passes `hand_size == 2` for NLH (mirroring `solve_state`'s own
+//! format check), in which case both route through the seven-card evaluator
+//! instead ([`omaha::monte_carlo_equity_holdem`] / `_seeded`) — the Omaha
+//! "exactly 2 from hand" rule would otherwise forbid playing the board or
+//! using only one hole card.
+
+use crate::omaha;
+use std::collections::HashMap;
+
+/// One of the five actions `SolveResult` reports frequencies for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    Raise,
+    AllIn,
+}
+
+fn action_index(a: Action) -> usize {
+    match a {
+        Action::Fold => 0,
+        Action::Check => 1,
+        Action::Call => 2,
+        Action::Raise => 3,
+        Action::AllIn => 4,
+    }
+}
+
+/// A decision point in the betting tree. `actions()` lists its legal moves
+/// in a fixed order, used to index both tables in `RegretEntry`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum NodeId {
+    /// Hero's opening action.
+    HeroRoot,
+    /// Villain facing hero's check.
+    VillainVsCheck,
+    /// Hero facing villain's bet, after hero checked.
+    HeroVsBetAfterCheck,
+    /// Villain facing hero's opening bet.
+    VillainVsBet,
+    /// Hero facing villain's raise of hero's opening bet.
+    HeroVsRaise,
+    /// Villain facing hero's opening shove.
+    VillainVsAllIn,
+    /// Villain facing hero's shove over villain's bet (after hero checked).
+    VillainVsAllInAfterCheck,
+}
+
+impl NodeId {
+    fn actions(self) -> &'static [Action] {
+        use Action::*;
+        match self {
+            NodeId::HeroRoot => &[Check, Raise, AllIn],
+            NodeId::VillainVsCheck => &[Check, Raise],
+            NodeId::HeroVsBetAfterCheck => &[Fold, Call, AllIn],
+            NodeId::VillainVsBet => &[Fold, Call, AllIn],
+            NodeId::HeroVsRaise => &[Fold, Call],
+            NodeId::VillainVsAllIn => &[Fold, Call],
+            NodeId::VillainVsAllInAfterCheck => &[Fold, Call],
+        }
+    }
+
+    /// True if hero (rather than villain) is on the move at this node.
+    fn is_hero(self) -> bool {
+        matches!(self, NodeId::HeroRoot | NodeId::HeroVsBetAfterCheck | NodeId::HeroVsRaise)
+    }
+
+    /// The node reached after playing a non-terminal `action` here, or
+    /// `None` if that `(node, action)` pair is terminal (see
+    /// `terminal_payoff`).
+    fn child(self, action: Action) -> Option<NodeId> {
+        use Action::*;
+        match (self, action) {
+            (NodeId::HeroRoot, Check) => Some(NodeId::VillainVsCheck),
+            (NodeId::HeroRoot, Raise) => Some(NodeId::VillainVsBet),
+            (NodeId::HeroRoot, AllIn) => Some(NodeId::VillainVsAllIn),
+            (NodeId::VillainVsCheck, Raise) => Some(NodeId::HeroVsBetAfterCheck),
+            (NodeId::HeroVsBetAfterCheck, AllIn) => Some(NodeId::VillainVsAllInAfterCheck),
+            (NodeId::VillainVsBet, AllIn) => Some(NodeId::HeroVsRaise),
+            _ => None,
+        }
+    }
+}
+
+/// Pot and bet sizes for one CFR solve, plus the hero-equity figure used to
+/// score showdown terminals this iteration (varies per iteration under
+/// CFR-CS, fixed under vanilla CFR).
+struct BettingContext {
+    pot0: f64,
+    bet: f64,
+    raise: f64,
+    stack: f64,
+    equity: f64,
+}
+
+/// Hero's showdown EV: win `equity` share of the final pot, otherwise give
+/// up `invested` — the same `equity*pot - (1-equity)*call_cost` shape
+/// `solver::compute_ev` already uses for its heuristic EV estimate.
+fn showdown_ev(equity: f64, pot: f64, invested: f64) -> f64 {
+    equity * pot - (1.0 - equity) * invested
+}
+
+/// Payoff for a terminal `(node, action)` pair, or `None` if the tree
+/// continues (see `NodeId::child`). All payoffs are hero-relative.
+fn terminal_payoff(ctx: &BettingContext, node: NodeId, action: Action) -> Option<f64> {
+    use Action::*;
+    use NodeId::*;
+    match (node, action) {
+        (VillainVsCheck, Check) => Some(showdown_ev(ctx.equity, ctx.pot0, 0.0)),
+        (HeroVsBetAfterCheck, Fold) => Some(0.0),
+        (HeroVsBetAfterCheck, Call) => Some(showdown_ev(ctx.equity, ctx.pot0 + 2.0 * ctx.bet, ctx.bet)),
+        (VillainVsBet, Fold) => Some(ctx.pot0),
+        (VillainVsBet, Call) => Some(showdown_ev(ctx.equity, ctx.pot0 + 2.0 * ctx.bet, ctx.bet)),
+        (HeroVsRaise, Fold) => Some(-ctx.bet),
+        (HeroVsRaise, Call) => {
+            Some(showdown_ev(ctx.equity, ctx.pot0 + 2.0 * ctx.bet + 2.0 * ctx.raise, ctx.raise))
+        }
+        (VillainVsAllIn, Fold) => Some(ctx.pot0),
+        (VillainVsAllIn, Call) => Some(showdown_ev(ctx.equity, ctx.pot0 + 2.0 * ctx.stack, ctx.stack)),
+        (VillainVsAllInAfterCheck, Fold) => Some(ctx.pot0 + ctx.bet),
+        (VillainVsAllInAfterCheck, Call) => {
+            Some(showdown_ev(ctx.equity, ctx.pot0 + 2.0 * ctx.stack, ctx.stack))
+        }
+        _ => None,
+    }
+}
+
+/// Per-info-set regret and strategy accumulators, indexed in lockstep with
+/// that info set's `NodeId::actions()`.
+#[derive(Debug, Clone)]
+struct RegretEntry {
+    regret_sum: Vec<f64>,
+    strategy_sum: Vec<f64>,
+}
+
+impl RegretEntry {
+    fn new(n: usize) -> Self {
+        Self { regret_sum: vec![0.0; n], strategy_sum: vec![0.0; n] }
+    }
+
+    /// Regret matching: positive-regret-weighted mix, uniform if every
+    /// regret is non-positive.
+    fn current_strategy(&self) -> Vec<f64> {
+        let positive: Vec<f64> = self.regret_sum.iter().map(|&r| r.max(0.0)).collect();
+        let total: f64 = positive.iter().sum();
+        let n = self.regret_sum.len();
+        if total > 0.0 {
+            positive.iter().map(|&p| p / total).collect()
+        } else {
+            vec![1.0 / n as f64; n]
+        }
+    }
+
+    fn average_strategy(&self) -> Vec<f64> {
+        let total: f64 = self.strategy_sum.iter().sum();
+        let n = self.strategy_sum.len();
+        if total > 0.0 {
+            self.strategy_sum.iter().map(|&s| s / total).collect()
+        } else {
+            vec![1.0 / n as f64; n]
+        }
+    }
+}
+
+/// Which payoff source backs showdown terminals during training.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// Every terminal reuses one precomputed multi-thousand-sim equity
+    /// estimate — a stand-in for enumerating every chance outcome.
+    Vanilla,
+    /// Every iteration draws a single fresh sampled runout
+    /// (`monte_carlo_equity_seeded` with `sims = 1`) instead, so showdown
+    /// payoffs vary from iteration to iteration. This adds one sim per
+    /// iteration on top of the precomputed estimate, which is still run
+    /// once to pick the info-set bucket.
+    ChanceSampled,
+}
+
+/// Bucket an equity value into one of 10 deciles.
+fn equity_bucket(equity: f64) -> u8 {
+    ((equity.clamp(0.0, 0.999_999) * 10.0) as u8).min(9)
+}
+
+/// Trains information sets across `solve()` calls (empty regret/strategy
+/// tables at construction). `solver::solve_state` builds a fresh one per
+/// decision, same as the heuristic it replaces; `historian.rs` keeps one
+/// per street and seeds it via [`CfrSolver::warm_started_from`].
+pub struct CfrSolver {
+    info_sets: HashMap<(NodeId, u8), RegretEntry>,
+}
+
+impl Default for CfrSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CfrSolver {
+    pub fn new() -> Self {
+        Self { info_sets: HashMap::new() }
+    }
+
+    /// A fresh solver pre-seeded with `parent`'s accumulated regret/strategy
+    /// tables rather than empty ones. Every info set here recurs node-for-
+    /// node (same `NodeId` shape) on every street this crate solves, so
+    /// last street's converged regrets are a far better starting point for
+    /// this street's traversal than uniform ones — see `historian.rs`, the
+    /// only caller of this so far.
+    pub fn warm_started_from(parent: &CfrSolver) -> Self {
+        Self { info_sets: parent.info_sets.clone() }
+    }
+
+    /// Whether any info set has been trained yet — used by `historian.rs`
+    /// to confirm a warm-started child actually inherited its parent's
+    /// tables rather than starting cold.
+    pub(crate) fn has_info_sets(&self) -> bool {
+        !self.info_sets.is_empty()
+    }
+
+    fn entry(&mut self, node: NodeId, bucket: u8) -> &mut RegretEntry {
+        self.info_sets.entry((node, bucket)).or_insert_with(|| RegretEntry::new(node.actions().len()))
+    }
+
+    /// One CFR traversal step. Returns hero's counterfactual value for
+    /// `node` and updates the regret/strategy tables for every info set
+    /// visited along the way.
+    fn cfr(&mut self, node: NodeId, ctx: &BettingContext, bucket: u8, hero_reach: f64, villain_reach: f64) -> f64 {
+        let actions = node.actions();
+        let strategy = self.entry(node, bucket).current_strategy();
+
+        let mut action_values = vec![0.0; actions.len()];
+        let mut node_value = 0.0;
+
+        for (i, &action) in actions.iter().enumerate() {
+            let value = match terminal_payoff(ctx, node, action) {
+                Some(payoff) => payoff,
+                None => {
+                    let child = node.child(action).expect("non-terminal action must have a child node");
+                    if node.is_hero() {
+                        self.cfr(child, ctx, bucket, hero_reach * strategy[i], villain_reach)
+                    } else {
+                        self.cfr(child, ctx, bucket, hero_reach, villain_reach * strategy[i])
+                    }
+                }
+            };
+            action_values[i] = value;
+            node_value += strategy[i] * value;
+        }
+
+        let entry = self.entry(node, bucket);
+        if node.is_hero() {
+            // Hero's own counterfactual value is `node_value`; the
+            // opponent's reach is villain_reach.
+            for i in 0..actions.len() {
+                entry.regret_sum[i] += villain_reach * (action_values[i] - node_value);
+                entry.strategy_sum[i] += hero_reach * strategy[i];
+            }
+        } else {
+            // Payoffs are hero-relative, so villain's own value is
+            // `-node_value`; flip the sign before computing regret.
+            for i in 0..actions.len() {
+                entry.regret_sum[i] += hero_reach * (node_value - action_values[i]);
+                entry.strategy_sum[i] += villain_reach * strategy[i];
+            }
+        }
+
+        node_value
+    }
+
+    /// Run `iterations` rounds of CFR and return the converged
+    /// `[fold, check, call, raise, allin]` frequency vector for hero's
+    /// opening decision. Fold and call are always 0.0 — there's nothing to
+    /// fold or call to until someone has bet.
+    #[allow(clippy::too_many_arguments)]
+    pub fn solve(
+        &mut self,
+        hero_cards: &[u8],
+        board_cards: &[u8],
+        dead_cards: &[u8],
+        pot_bb100: u32,
+        hero_stack: u32,
+        villain_stack: u32,
+        opponents: usize,
+        hand_size: usize,
+        iterations: u32,
+        variant: Variant,
+    ) -> [f64; 5] {
+        let pot0 = (pot_bb100.max(1)) as f64;
+        let stack = (hero_stack.min(villain_stack).max(1)) as f64;
+        let bet = (pot0 * 0.75).min(stack);
+        let raise = (pot0 + 2.0 * bet).min((stack - bet).max(0.0));
+
+        // NLH ranks best-of-7 (no Omaha "exactly 2 from hand" rule), so it
+        // routes through the seven-card evaluator instead —
+        // same format check `solve_state` makes before calling in.
+        let is_holdem = hand_size == 2;
+
+        let vanilla_equity = if is_holdem {
+            omaha::monte_carlo_equity_holdem(hero_cards, board_cards, dead_cards, 4000, opponents)
+        } else {
+            omaha::monte_carlo_equity(hero_cards, board_cards, dead_cards, 4000, opponents, hand_size)
+        };
+        let bucket = equity_bucket(vanilla_equity);
+
+        for i in 0..iterations {
+            let equity = match variant {
+                Variant::Vanilla => vanilla_equity,
+                Variant::ChanceSampled if is_holdem => omaha::monte_carlo_equity_holdem_seeded(
+                    hero_cards,
+                    board_cards,
+                    dead_cards,
+                    1,
+                    opponents,
+                    i as u64,
+                ),
+                Variant::ChanceSampled => omaha::monte_carlo_equity_seeded(
+                    hero_cards,
+                    board_cards,
+                    dead_cards,
+                    1,
+                    opponents,
+                    hand_size,
+                    i as u64,
+                ),
+            };
+            let ctx = BettingContext { pot0, bet, raise, stack, equity };
+            self.cfr(NodeId::HeroRoot, &ctx, bucket, 1.0, 1.0);
+        }
+
+        let avg = self
+            .info_sets
+            .get(&(NodeId::HeroRoot, bucket))
+            .map(RegretEntry::average_strategy)
+            .unwrap_or_else(|| vec![1.0 / 3.0; 3]); // iterations == 0: root never visited
+
+        let mut freq = [0.0; 5];
+        for (i, &action) in NodeId::HeroRoot.actions().iter().enumerate() {
+            freq[action_index(action)] = avg[i];
+        }
+        freq
+    }
+
+    /// The bet size (bb×100) this solve's opening-bet and all-in lines used —
+    /// so callers can report a `raise_amount_bb100` consistent with the
+    /// strategy CFR actually converged on.
+    pub fn opening_bet_bb100(pot_bb100: u32, hero_stack: u32, villain_stack: u32) -> u32 {
+        let pot0 = (pot_bb100.max(1)) as f64;
+        let stack = (hero_stack.min(villain_stack).max(1)) as f64;
+        (pot0 * 0.75).min(stack) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluator;
+
+    fn setup() {
+        evaluator::init_tables();
+    }
+
+    #[test]
+    fn test_frequencies_sum_to_one_and_fold_call_are_zero() {
+        setup();
+        let hero = vec![48, 49, 40, 36, 32]; // A♣ A♦ Q♣ J♣ T♣
+        let board = vec![50, 44, 38]; // A♥ K♣ J♥
+
+        let mut solver = CfrSolver::new();
+        let freq = solver.solve(&hero, &board, &[], 1000, 10000, 10000, 1, 5, 200, Variant::Vanilla);
+
+        let sum: f64 = freq.iter().sum();
+        assert!((sum - 1.0).abs() < 1e-6, "frequencies should sum to 1.0, got {:?}", freq);
+        assert_eq!(freq[0], 0.0, "fold isn't legal at the root");
+        assert_eq!(freq[2], 0.0, "call isn't legal at the root");
+    }
+
+    #[test]
+    fn test_strong_hand_favors_aggression_over_check() {
+        setup();
+        // Flopped the nuts (trip Aces with a redraw) — should want to build
+        // the pot rather than check it down.
+        let hero = vec![48, 49, 40, 36, 32];
+        let board = vec![50, 44, 38];
+
+        let mut solver = CfrSolver::new();
+        let freq = solver.solve(&hero, &board, &[], 1000, 10000, 10000, 1, 5, 300, Variant::Vanilla);
+
+        let aggression = freq[3] + freq[4]; // raise + allin
+        assert!(
+            aggression > freq[1],
+            "premium hand should bet/shove more than check, got freq={:?}",
+            freq
+        );
+    }
+
+    #[test]
+    fn test_chance_sampled_converges_near_vanilla() {
+        setup();
+        let hero = vec![48, 49, 40, 36, 32];
+        let board = vec![50, 44, 38];
+
+        let mut vanilla = CfrSolver::new();
+        let vanilla_freq = vanilla.solve(&hero, &board, &[], 1000, 10000, 10000, 1, 5, 500, Variant::Vanilla);
+
+        let mut sampled = CfrSolver::new();
+        let sampled_freq =
+            sampled.solve(&hero, &board, &[], 1000, 10000, 10000, 1, 5, 2000, Variant::ChanceSampled);
+
+        for i in 0..5 {
+            assert!(
+                (vanilla_freq[i] - sampled_freq[i]).abs() < 0.15,
+                "CFR-CS should roughly track vanilla CFR at slot {}: vanilla={:?} sampled={:?}",
+                i,
+                vanilla_freq,
+                sampled_freq
+            );
+        }
+    }
+
+    #[test]
+    fn test_warm_started_solver_retains_parents_info_sets() {
+        setup();
+        let hero = vec![48, 49, 40, 36, 32];
+        let board = vec![50, 44, 38];
+
+        let mut parent = CfrSolver::new();
+        parent.solve(&hero, &board, &[], 1000, 10000, 10000, 1, 5, 200, Variant::Vanilla);
+        assert!(!parent.info_sets.is_empty());
+
+        let child = CfrSolver::warm_started_from(&parent);
+        assert_eq!(child.info_sets.len(), parent.info_sets.len());
+    }
+
+    #[test]
+    fn test_zero_iterations_returns_uniform_over_root_actions() {
+        setup();
+        let hero = vec![48, 49, 40, 36, 32];
+        let board = vec![50, 44, 38];
+
+        let mut solver = CfrSolver::new();
+        let freq = solver.solve(&hero, &board, &[], 1000, 10000, 10000, 1, 5, 0, Variant::Vanilla);
+
+        assert!((freq[1] - 1.0 / 3.0).abs() < 1e-9);
+        assert!((freq[3] - 1.0 / 3.0).abs() < 1e-9);
+        assert!((freq[4] - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_nlh_hand_size_uses_holdem_showdown_equity() {
+        setup();
+        // Board alone is quad aces — Hold'em's best-of-7 rule lets hero play
+        // it as-is, but Omaha's "exactly 2 from hand, 3 from board" would
+        // force diluting it with hero's garbage hole cards instead. A CFR
+        // solve that mistakenly routed `hand_size == 2` through the Omaha
+        // evaluator would see a weak hand here and favor checking.
+        let hero = vec![0, 5]; // 2♣ 3♦
+        let board = vec![48, 49, 50, 51, 44]; // A♣ A♦ A♥ A♠ K♣
+
+        let mut solver = CfrSolver::new();
+        let freq = solver.solve(&hero, &board, &[], 1000, 10000, 10000, 1, 2, 300, Variant::Vanilla);
+
+        let sum: f64 = freq.iter().sum();
+        assert!((sum - 1.0).abs() < 1e-6, "frequencies should sum to 1.0, got {:?}", freq);
+
+        let aggression = freq[3] + freq[4]; // raise + allin
+        assert!(
+            aggression > freq[1],
+            "quads on board should bet/shove more than check, got freq={:?}",
+            freq
+        );
+    }
+}
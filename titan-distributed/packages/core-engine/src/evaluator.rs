@@ -42,15 +42,42 @@ const RANK_BITS: [u32; 13] = [
 ];
 
 // Pre-computed flush and unique5 lookup tables
-// These are generated in init_tables() 
+// These are generated in init_tables()
 static mut FLUSH_TABLE: [u16; 8192] = [0u16; 8192];
 static mut UNIQUE5_TABLE: [u16; 8192] = [0u16; 8192];
 
+// Sorted (prime_product, rank) pairs for every non-flush, non-unique-rank
+// hand (quads, full houses, trips, two pair, one pair). Filled once by
+// `generate_prime_table()`; `lookup_prime_product` binary-searches it.
+static mut PRIME_TABLE: Vec<(u64, u16)> = Vec::new();
+
+// One random 64-bit key per card ID (0-51), used by the Zobrist eval cache
+// below. Filled once by `generate_zobrist_keys()`.
+static mut ZOBRIST_KEYS: [u64; 52] = [0u64; 52];
+
+// Fixed-size open-addressing cache: slot `hash & (EVAL_CACHE_SIZE - 1)` holds
+// the last `(hash, rank)` written to it. A direct-mapped cache (no probing)
+// keeps lookups to one array access; a collision just evicts, it never
+// returns a wrong answer because the full hash is also stored and checked.
+//
+// Sizing tradeoff: 65536 slots (24 bytes each, ~1.5MB) covers a solid fraction of the
+// 2,598,960 distinct 5-card combinations without the memory cost of caching
+// them all. Repeated (hole-pair, board-triple) combos are common within a
+// single Monte Carlo run — PLO6 alone re-evaluates the same 5-card combo
+// across many of its 150 evals/hand — so even a partial cache turns a good
+// share of `evaluate_5cards` calls into a single lookup. Raise this if
+// profiling shows a low hit rate for a given workload; it trades memory for
+// fewer collisions.
+const EVAL_CACHE_SIZE: usize = 1 << 16;
+static mut EVAL_CACHE: Vec<Option<(u64, u16)>> = Vec::new();
+
 /// Initialize lookup tables. Must be called once at startup.
 pub fn init_tables() {
     INIT.call_once(|| {
         generate_flush_table();
         generate_unique5_table();
+        generate_prime_table();
+        generate_zobrist_keys();
         log::info!("Evaluator lookup tables initialized (32KB)");
     });
 }
@@ -107,6 +134,33 @@ pub fn evaluate_5cards(c0: usize, c1: usize, c2: usize, c3: usize, c4: usize) ->
     lookup_prime_product(prime_product)
 }
 
+/// Same result as [`evaluate_5cards`], but checks the Zobrist-keyed eval
+/// cache first. Card-set XOR is order-independent, so the same five cards
+/// always hash to the same slot regardless of argument order.
+///
+/// Intended for hot paths that re-evaluate the same 5-card combos many
+/// times in a short window (e.g. Monte Carlo equity across thousands of
+/// sims) — see the cache-size tradeoff note on [`EVAL_CACHE_SIZE`].
+#[inline]
+pub fn evaluate_5cards_cached(c0: usize, c1: usize, c2: usize, c3: usize, c4: usize) -> u16 {
+    unsafe {
+        let keys = &*std::ptr::addr_of!(ZOBRIST_KEYS);
+        let hash = keys[c0] ^ keys[c1] ^ keys[c2] ^ keys[c3] ^ keys[c4];
+        let slot = (hash as usize) & (EVAL_CACHE_SIZE - 1);
+
+        let cache = &mut *std::ptr::addr_of_mut!(EVAL_CACHE);
+        if let Some((cached_hash, rank)) = cache[slot] {
+            if cached_hash == hash {
+                return rank;
+            }
+        }
+
+        let rank = evaluate_5cards(c0, c1, c2, c3, c4);
+        cache[slot] = Some((hash, rank));
+        rank
+    }
+}
+
 // ── Lookup Table Generation ─────────────────────────────────────────
 
 fn generate_flush_table() {
@@ -205,74 +259,128 @@ fn generate_unique5_table() {
 }
 
 /// Lookup paired/tripped/quaded hands by prime product hash.
-/// Uses binary search on a pre-sorted table of (prime_product, rank) pairs.
+/// Binary search over the pre-sorted `PRIME_TABLE` built by
+/// `generate_prime_table()`. Every non-flush, non-unique-rank 5-card combo
+/// has exactly one entry, so this always finds an exact match.
 fn lookup_prime_product(product: u64) -> u16 {
-    // This table maps prime products to hand ranks for all non-unique hands.
-    // Generated at compile time. Contains all paired combos:
-    //   - Four of a Kind:  13 × choices = ~156 entries
-    //   - Full House:      13 × 12 = 156 entries
-    //   - Three of a Kind: C(13,1)×C(12,2) = 858 entries
-    //   - Two Pair:        C(13,2)×11 = 858 entries
-    //   - One Pair:        13 × C(12,3) = 2860 entries
-    //
-    // Total: ~4888 entries. Binary search = O(log 4888) ≈ 12 comparisons.
-
-    // For the initial implementation, use a simplified approach:
-    // Count rank occurrences to classify hand type, then rank within type.
-    classify_by_counts(product)
+    unsafe {
+        let table: &[(u64, u16)] = &*std::ptr::addr_of!(PRIME_TABLE);
+        table
+            .binary_search_by_key(&product, |&(p, _)| p)
+            .map(|i| table[i].1)
+            .unwrap_or(7000) // unreachable for a valid 5-card hand
+    }
 }
 
-/// Classify a hand by rank counts when prime lookup table isn't loaded.
-fn classify_by_counts(prime_product: u64) -> u16 {
-    // Factor the prime product to recover rank counts
-    let mut counts = [0u8; 13];
-    let mut remaining = prime_product;
-
-    for (i, &p) in RANK_PRIMES.iter().enumerate() {
-        while remaining % p as u64 == 0 {
-            counts[i] += 1;
-            remaining /= p as u64;
+/// Build the perfect-hash table covering every non-flush, non-unique-rank
+/// hand: quads, full houses, trips, two pair, one pair. Ranks are assigned
+/// in strength order within each bucket (primary rank, then secondary rank,
+/// then kickers, all descending) to match the documented rank ranges:
+///   - Four of a Kind:  11-166    (13 × 12 = 156)
+///   - Full House:      167-322   (13 × 12 = 156)
+///   - Three of a Kind: 1610-2467 (13 × C(12,2) = 858)
+///   - Two Pair:        2468-3325 (C(13,2) × 11 = 858)
+///   - One Pair:        3326-6185 (13 × C(12,3) = 2860)
+///
+/// Total: 4888 entries, sorted by prime product for binary search.
+fn generate_prime_table() {
+    let p = |r: usize| RANK_PRIMES[r] as u64;
+    let mut table = Vec::with_capacity(4888);
+
+    // Four of a Kind: quad rank, then kicker, both descending.
+    let mut rank = 11u16;
+    for quad in (0..13).rev() {
+        for kicker in (0..13).rev() {
+            if kicker == quad {
+                continue;
+            }
+            table.push((p(quad).pow(4) * p(kicker), rank));
+            rank += 1;
         }
     }
 
-    // Sort counts descending to identify hand pattern
-    let mut sorted_counts = counts.iter().copied()
-        .filter(|&c| c > 0)
-        .collect::<Vec<_>>();
-    sorted_counts.sort_unstable_by(|a, b| b.cmp(a));
-
-    match sorted_counts.as_slice() {
-        [4, 1] => {
-            // Four of a Kind: rank 11-166
-            let quad_rank = counts.iter().position(|&c| c == 4).unwrap_or(0);
-            11 + (12 - quad_rank as u16) * 12
-        }
-        [3, 2] => {
-            // Full House: rank 167-322
-            let trips_rank = counts.iter().position(|&c| c == 3).unwrap_or(0);
-            let pair_rank = counts.iter().position(|&c| c == 2).unwrap_or(0);
-            167 + (12 - trips_rank as u16) * 12 + (12 - pair_rank as u16)
+    // Full House: trips rank, then pair rank, both descending.
+    debug_assert_eq!(rank, 167);
+    for trips in (0..13).rev() {
+        for pair in (0..13).rev() {
+            if pair == trips {
+                continue;
+            }
+            table.push((p(trips).pow(3) * p(pair).pow(2), rank));
+            rank += 1;
         }
-        [3, 1, 1] => {
-            // Three of a Kind: rank 1610-2467
-            let trips_rank = counts.iter().position(|&c| c == 3).unwrap_or(0);
-            1610 + (12 - trips_rank as u16) * 66
+    }
+
+    // Three of a Kind: trips rank, then the two kickers (descending).
+    // Ranks 323-1609 (flushes, straights) live in the other two tables.
+    debug_assert_eq!(rank, 323);
+    rank = 1610;
+    for trips in (0..13).rev() {
+        let kickers: Vec<usize> = (0..13).rev().filter(|&r| r != trips).collect();
+        for i in 0..kickers.len() {
+            for j in (i + 1)..kickers.len() {
+                table.push((p(trips).pow(3) * p(kickers[i]) * p(kickers[j]), rank));
+                rank += 1;
+            }
         }
-        [2, 2, 1] => {
-            // Two Pair: rank 2468-3325
-            let pairs: Vec<usize> = counts.iter().enumerate()
-                .filter(|(_, &c)| c == 2)
-                .map(|(i, _)| i)
-                .collect();
-            let hi = pairs.iter().copied().max().unwrap_or(0);
-            2468 + (12 - hi as u16) * 66
+    }
+
+    // Two Pair: high pair, then low pair, then kicker, all descending.
+    debug_assert_eq!(rank, 2468);
+    for hi in (0..13).rev() {
+        for lo in (0..hi).rev() {
+            for kicker in (0..13).rev() {
+                if kicker == hi || kicker == lo {
+                    continue;
+                }
+                table.push((p(hi).pow(2) * p(lo).pow(2) * p(kicker), rank));
+                rank += 1;
+            }
         }
-        [2, 1, 1, 1] => {
-            // One Pair: rank 3326-6185
-            let pair_rank = counts.iter().position(|&c| c == 2).unwrap_or(0);
-            3326 + (12 - pair_rank as u16) * 220
+    }
+
+    // One Pair: pair rank, then the three kickers (descending).
+    debug_assert_eq!(rank, 3326);
+    for pair in (0..13).rev() {
+        let kickers: Vec<usize> = (0..13).rev().filter(|&r| r != pair).collect();
+        for i in 0..kickers.len() {
+            for j in (i + 1)..kickers.len() {
+                for k in (j + 1)..kickers.len() {
+                    table.push((
+                        p(pair).pow(2) * p(kickers[i]) * p(kickers[j]) * p(kickers[k]),
+                        rank,
+                    ));
+                    rank += 1;
+                }
+            }
         }
-        _ => 7000, // fallback
+    }
+    debug_assert_eq!(rank, 6186);
+
+    table.sort_unstable_by_key(|&(product, _)| product);
+    unsafe {
+        PRIME_TABLE = table;
+    }
+}
+
+/// Fill `ZOBRIST_KEYS` with 52 pseudo-random 64-bit keys (xorshift64,
+/// fixed seed) and allocate an empty `EVAL_CACHE`. A fixed seed keeps
+/// `evaluate_5cards_cached` deterministic across runs — it's a cache key,
+/// not a security token, so reproducibility matters more than entropy
+/// source.
+fn generate_zobrist_keys() {
+    let mut state: u64 = 0x9E3779B97F4A7C15; // golden ratio constant, nonzero seed
+    let mut keys = [0u64; 52];
+    for key in keys.iter_mut() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        *key = state;
+    }
+
+    unsafe {
+        ZOBRIST_KEYS = keys;
+        EVAL_CACHE = vec![None; EVAL_CACHE_SIZE];
     }
 }
 
@@ -325,4 +433,53 @@ mod tests {
         assert!(rf < fh, "Royal ({}) < Full House ({})", rf, fh);
         assert!(fh < pair, "Full House ({}) < Pair ({})", fh, pair);
     }
+
+    #[test]
+    fn test_one_pair_kickers_break_ties() {
+        setup();
+        // Pair of Aces, kicker K beats pair of Aces, kicker Q.
+        let pair_ak = evaluate_5cards(48, 49, 45, 38, 30); // A♣ A♦ K♦ J♥ 9♥
+        let pair_aq = evaluate_5cards(48, 49, 41, 38, 30); // A♣ A♦ Q♦ J♥ 9♥
+        assert!(
+            pair_ak < pair_aq,
+            "Better kicker ({}) should beat worse kicker ({})",
+            pair_ak,
+            pair_aq
+        );
+    }
+
+    #[test]
+    fn test_two_pair_ranked_by_top_pair_then_kicker() {
+        setup();
+        // Aces-and-Kings beats Aces-and-Queens; both beat Kings-and-Queens.
+        let aces_kings = evaluate_5cards(48, 49, 45, 46, 38); // AA KK J
+        let aces_queens = evaluate_5cards(48, 49, 41, 42, 38); // AA QQ J
+        let kings_queens = evaluate_5cards(45, 46, 41, 42, 38); // KK QQ J
+        assert!(aces_kings < aces_queens, "AA+KK ({}) should beat AA+QQ ({})", aces_kings, aces_queens);
+        assert!(aces_queens < kings_queens, "AA+QQ ({}) should beat KK+QQ ({})", aces_queens, kings_queens);
+    }
+
+    #[test]
+    fn test_four_of_a_kind_kicker_breaks_tie() {
+        setup();
+        let quad_aces_k = evaluate_5cards(48, 49, 50, 51, 45); // AAAA K
+        let quad_aces_q = evaluate_5cards(48, 49, 50, 51, 41); // AAAA Q
+        assert!(
+            quad_aces_k < quad_aces_q,
+            "Quad Aces with K kicker ({}) should beat Q kicker ({})",
+            quad_aces_k,
+            quad_aces_q
+        );
+    }
+
+    #[test]
+    fn test_cached_matches_uncached() {
+        setup();
+        // Same five cards, evaluated once uncached and repeatedly cached —
+        // the cache must never change the answer, regardless of argument order.
+        let rank = evaluate_5cards(48, 49, 43, 39, 35);
+        assert_eq!(evaluate_5cards_cached(48, 49, 43, 39, 35), rank);
+        assert_eq!(evaluate_5cards_cached(48, 49, 43, 39, 35), rank); // cache hit
+        assert_eq!(evaluate_5cards_cached(35, 39, 43, 49, 48), rank); // different order, same set
+    }
 }
@@ -0,0 +1,290 @@
+//! Titan Core Engine — Action Historian & Persistent Game Tree
+//!
+//! `solver::solve_state` re-solves [`crate::cfr::CfrSolver`] from scratch on
+//! every call — fine for one decision, wasteful for an online agent tracking
+//! a single hand through flop → turn → river. This module keeps a live
+//! hand's state in an arena-backed [`GameNode`] tree addressed by index
+//! (so growing the tree never invalidates an earlier reference) and exposes
+//! [`Historian`] as a cursor into it: `deal_board` moves the cursor to the
+//! next street's node, `advance_hero`/`advance_villain` record the betting
+//! observed at the current node, and `solve_current` runs CFR at wherever
+//! the cursor is now.
+//!
+//! ## Scope
+//!
+//! A [`GameNode`] represents one street's decision, matching
+//! `CfrSolver`'s own per-street tree (one opening action, at most one raise
+//! per side, capped at all-in) — so only `deal_board` actually advances the
+//! cursor to a new node. `advance_hero`/`advance_villain` update the current
+//! node's pot/stack bookkeeping as the street's betting unfolds, without
+//! changing which node is current: that betting sequence is exactly what
+//! `CfrSolver::solve` already resolves in one call by building and
+//! traversing its own multi-action tree, so the historian has no separate
+//! use for the specific action taken.
+//!
+//! Each new node's solver is seeded via
+//! [`crate::cfr::CfrSolver::warm_started_from`] the parent's, so solving
+//! turn reuses whatever flop already converged instead of starting over.
+//!
+//! ## Future: N-API exposure beyond this class
+//!
+//! `Historian` is this crate's first stateful `#[napi]` type — every other
+//! export in `lib.rs` is a pure function. That's deliberate: the free
+//! functions model one decision in, one decision out, while an online agent
+//! tracking a live hand genuinely needs a handle that outlives a single
+//! call. Revisit the free functions if more stateful features show up.
+
+use crate::cfr::{self, CfrSolver};
+use napi_derive::napi;
+
+/// One node in the [`Historian`]'s tree: the state reached after some
+/// prefix of observed streets, plus the CFR solver warm-starting this
+/// street's decision.
+struct GameNode {
+    street: u32,
+    board_cards: Vec<u8>,
+    pot_bb100: u32,
+    hero_stack: u32,
+    villain_stack: u32,
+    /// `(newly dealt cards, child index)` — searched linearly since a real
+    /// hand only ever takes one runout; this only grows if a caller
+    /// re-explores an alternate board from the same ancestor.
+    children: Vec<(Vec<u8>, usize)>,
+    solver: CfrSolver,
+}
+
+impl GameNode {
+    fn root(pot_bb100: u32, hero_stack: u32, villain_stack: u32, board_cards: Vec<u8>) -> Self {
+        let street = match board_cards.len() {
+            0 => 0,
+            3 => 1,
+            4 => 2,
+            _ => 3,
+        };
+        Self {
+            street,
+            board_cards,
+            pot_bb100,
+            hero_stack,
+            villain_stack,
+            children: Vec::new(),
+            solver: CfrSolver::new(),
+        }
+    }
+}
+
+/// Arena-backed tree of [`GameNode`]s, addressed by index rather than
+/// pointer.
+struct GameTree {
+    nodes: Vec<GameNode>,
+}
+
+impl GameTree {
+    /// The child of `parent` reached by dealing `cards`, creating (and
+    /// warm-starting from `parent`) one if it doesn't exist yet.
+    fn child_or_insert(&mut self, parent: usize, cards: &[u8]) -> usize {
+        if let Some(&(_, idx)) = self.nodes[parent].children.iter().find(|(c, _)| c == cards) {
+            return idx;
+        }
+
+        let (street, mut board_cards, pot_bb100, hero_stack, villain_stack, warm_solver) = {
+            let p = &self.nodes[parent];
+            (
+                p.street + 1,
+                p.board_cards.clone(),
+                p.pot_bb100,
+                p.hero_stack,
+                p.villain_stack,
+                CfrSolver::warm_started_from(&p.solver),
+            )
+        };
+        board_cards.extend_from_slice(cards);
+
+        let idx = self.nodes.len();
+        self.nodes.push(GameNode {
+            street,
+            board_cards,
+            pot_bb100,
+            hero_stack,
+            villain_stack,
+            children: Vec::new(),
+            solver: warm_solver,
+        });
+        self.nodes[parent].children.push((cards.to_vec(), idx));
+        idx
+    }
+}
+
+/// A cursor into a live hand's [`GameTree`]. Construct once per hand, then
+/// drive it forward with `deal_board`/`advance_hero`/`advance_villain` as
+/// actions are observed, and call `solve_current` to get the equilibrium
+/// strategy at wherever the hand currently stands.
+#[napi]
+pub struct Historian {
+    tree: GameTree,
+    cursor: usize,
+}
+
+#[napi]
+impl Historian {
+    /// Start tracking a new hand from its current pot/stacks and any board
+    /// cards already known (empty preflop).
+    #[napi(constructor)]
+    pub fn new(pot_bb100: u32, hero_stack: u32, villain_stack: u32, board_cards: Vec<u8>) -> Self {
+        let root = GameNode::root(pot_bb100, hero_stack, villain_stack, board_cards);
+        Self { tree: GameTree { nodes: vec![root] }, cursor: 0 }
+    }
+
+    /// Record an observed hero action and the resulting pot/stacks, without
+    /// changing which street is current — see the module's "Scope" note.
+    #[napi]
+    pub fn advance_hero(&mut self, pot_bb100: u32, hero_stack: u32, villain_stack: u32) {
+        self.update_current(pot_bb100, hero_stack, villain_stack);
+    }
+
+    /// Record an observed villain action and the resulting pot/stacks, same
+    /// as `advance_hero` but for the other side.
+    #[napi]
+    pub fn advance_villain(&mut self, pot_bb100: u32, hero_stack: u32, villain_stack: u32) {
+        self.update_current(pot_bb100, hero_stack, villain_stack);
+    }
+
+    /// Record new board cards being revealed, moving the cursor to (and
+    /// warm-starting, if new) the node for the resulting street.
+    #[napi]
+    pub fn deal_board(&mut self, cards: Vec<u8>, pot_bb100: u32, hero_stack: u32, villain_stack: u32) {
+        self.cursor = self.tree.child_or_insert(self.cursor, &cards);
+        self.update_current(pot_bb100, hero_stack, villain_stack);
+    }
+
+    fn update_current(&mut self, pot_bb100: u32, hero_stack: u32, villain_stack: u32) {
+        let node = &mut self.tree.nodes[self.cursor];
+        node.pot_bb100 = pot_bb100;
+        node.hero_stack = hero_stack;
+        node.villain_stack = villain_stack;
+    }
+
+    /// Run CFR at the current node and return the converged
+    /// `[fold, check, call, raise, allin]` frequencies — see
+    /// [`crate::cfr::CfrSolver::solve`].
+    #[napi]
+    pub fn solve_current(
+        &mut self,
+        hero_cards: Vec<u8>,
+        dead_cards: Vec<u8>,
+        opponents: u32,
+        hand_size: u32,
+        iterations: u32,
+    ) -> Vec<f64> {
+        let node = &mut self.tree.nodes[self.cursor];
+        let freq = node.solver.solve(
+            &hero_cards,
+            &node.board_cards,
+            &dead_cards,
+            node.pot_bb100,
+            node.hero_stack,
+            node.villain_stack,
+            opponents as usize,
+            hand_size as usize,
+            iterations,
+            cfr::Variant::Vanilla,
+        );
+        freq.to_vec()
+    }
+
+    /// 0=Preflop, 1=Flop, 2=Turn, 3=River — matches `SolveParams::street`.
+    #[napi]
+    pub fn current_street(&self) -> u32 {
+        self.tree.nodes[self.cursor].street
+    }
+
+    /// The full board as observed so far at the current node.
+    #[napi]
+    pub fn current_board(&self) -> Vec<u8> {
+        self.tree.nodes[self.cursor].board_cards.clone()
+    }
+
+    /// Whether the current node's solver already holds trained info sets,
+    /// either inherited from an ancestor street or from an earlier
+    /// `solve_current` at this node. A caller can spend fewer `iterations`
+    /// on a warm-started node than on a cold one.
+    #[napi]
+    pub fn is_warm_started(&self) -> bool {
+        self.tree.nodes[self.cursor].solver.has_info_sets()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluator;
+
+    fn setup() {
+        evaluator::init_tables();
+    }
+
+    #[test]
+    fn test_deal_board_advances_cursor_and_street() {
+        let mut h = Historian::new(1000, 10000, 10000, vec![]);
+        assert_eq!(h.current_street(), 0);
+
+        h.deal_board(vec![50, 44, 38], 1000, 10000, 10000); // flop
+        assert_eq!(h.current_street(), 1);
+        assert_eq!(h.current_board(), vec![50, 44, 38]);
+
+        h.deal_board(vec![4], 1400, 9600, 9600); // turn
+        assert_eq!(h.current_street(), 2);
+        assert_eq!(h.current_board(), vec![50, 44, 38, 4]);
+    }
+
+    #[test]
+    fn test_advance_hero_and_villain_update_state_without_changing_street() {
+        let mut h = Historian::new(1000, 10000, 10000, vec![]);
+        h.advance_hero(2000, 9000, 10000);
+        h.advance_villain(4000, 9000, 9000);
+        assert_eq!(h.current_street(), 0);
+        assert_eq!(h.tree.nodes[h.cursor].pot_bb100, 4000);
+        assert_eq!(h.tree.nodes[h.cursor].hero_stack, 9000);
+        assert_eq!(h.tree.nodes[h.cursor].villain_stack, 9000);
+    }
+
+    #[test]
+    fn test_dealing_the_same_board_twice_reuses_the_cached_node() {
+        let mut h = Historian::new(1000, 10000, 10000, vec![]);
+        h.deal_board(vec![50, 44, 38], 1000, 10000, 10000);
+        let first = h.cursor;
+
+        h.cursor = 0; // re-explore the same flop from the root
+        h.deal_board(vec![50, 44, 38], 1000, 10000, 10000);
+        assert_eq!(h.cursor, first, "identical board should hit the cached child");
+    }
+
+    #[test]
+    fn test_solve_current_runs_at_the_cursors_board_and_pot() {
+        setup();
+        let mut h = Historian::new(1000, 10000, 10000, vec![]);
+        h.deal_board(vec![50, 44, 38], 1000, 10000, 10000);
+
+        let hero = vec![48, 49, 40, 36]; // A♣ A♦ Q♣ J♣
+        let freq = h.solve_current(hero, vec![], 1, 4, 50);
+
+        let sum: f64 = freq.iter().sum();
+        assert!((sum - 1.0).abs() < 1e-6, "frequencies should sum to 1.0, got {:?}", freq);
+    }
+
+    #[test]
+    fn test_warm_started_child_skips_cold_start() {
+        setup();
+        let hero = vec![48, 49, 40, 36];
+        let mut h = Historian::new(1000, 10000, 10000, vec![]);
+
+        // Converge preflop first so the flop node inherits real regrets.
+        h.solve_current(hero.clone(), vec![], 1, 4, 200);
+        h.deal_board(vec![50, 44, 38], 1000, 10000, 10000);
+
+        assert!(
+            h.is_warm_started(),
+            "flop node should start from the preflop node's converged info sets"
+        );
+    }
+}
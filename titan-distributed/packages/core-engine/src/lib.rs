@@ -13,11 +13,15 @@
 //! lib.rs  ──────────────────────────  This file (entry point)
 //!     ├── evaluator.rs               5-card hand evaluator (bitwise)
 //!     ├── omaha.rs                   Omaha C(hand,2)×C(board,3) logic
+//!     ├── seven_eval.rs              NLH 7-card (best-of-21) evaluator
+//!     ├── ranges.rs                  Compact range notation parser
+//!     ├── pushfold.rs                Short-stack jam/fold Nash equilibrium
+//!     ├── historian.rs               Persistent game tree for live hands
+//!     │                              (stateful `#[napi]` class, unlike
+//!     │                              everything else below)
 //!     ├── solver.rs                  Monte Carlo equity + strategy
-//!     └── cfr/
-//!         ├── deep_cfr.rs            Deep CFR neural network lookup
-//!         ├── abstraction.rs         Hand abstraction (isomorphism)
-//!         └── strategy.rs            Strategy table storage
+//!     └── cfr.rs                     Regret-matching CFR solver (opt-in
+//!                                    via `SolveParams::cfr_iterations`)
 //! ```
 //!
 //! ## Performance
@@ -26,8 +30,13 @@
 //! - PLO5 full equity (5000 sims): ~3ms (vs ~170ms in JS)
 //! - Deep CFR lookup: <1ms (pre-loaded tables)
 
+mod cfr;
 mod evaluator;
+mod historian;
 mod omaha;
+mod pushfold;
+mod ranges;
+mod seven_eval;
 mod solver;
 
 use napi::bindgen_prelude::*;
@@ -70,6 +79,84 @@ pub struct SolveParams {
     /// Number of players remaining (alias: num_opponents — adds 1 internally)
     #[serde(alias = "num_opponents")]
     pub num_players: u32,
+    /// Per-opponent weighted hand range, indexed in villain order. An empty
+    /// (or absent) entry models that opponent as fully random, matching the
+    /// pre-range behavior.
+    #[serde(default)]
+    pub villain_ranges: Vec<Vec<VillainRangeCombo>>,
+    /// Per-opponent compact range notation (e.g. `"66+,A8s+,AJo+,KQs"`),
+    /// indexed in villain order, merged with `villain_ranges` for the same
+    /// opponent. Only valid for 2-card notation — PLO opponents still need
+    /// explicit combos via `villain_ranges`. See [`crate::ranges`].
+    #[serde(default)]
+    pub villain_range_notation: Vec<String>,
+    /// Run [`crate::cfr::CfrSolver`] for this many iterations instead of the
+    /// heuristic `compute_strategy`. `0` (the default) keeps the fast
+    /// heuristic path.
+    ///
+    /// This is *not* a full two-player Nash equilibrium solve: both hero's
+    /// and villain's info sets are keyed by hero's equity bucket (see
+    /// `cfr::CfrSolver`'s module docs), so villain's node is effectively
+    /// indexed by hero's hand strength rather than villain's own. The
+    /// output is a best response to that scoped-down tree, not a real
+    /// two-player equilibrium — don't over-trust it as "solved" game theory.
+    #[serde(default)]
+    pub cfr_iterations: u32,
+    /// Discrete bet sizes to split the raise/all-in frequency across for a
+    /// given street, in place of the single heuristic `raise_amount_bb100`.
+    /// Absent or no entry for the current street keeps the single-size
+    /// behavior. Only consulted by the heuristic `compute_strategy` path
+    /// (`cfr_iterations == 0`); see [`BetSizeCandidates`].
+    #[serde(default)]
+    pub bet_size_candidates: Vec<BetSizeCandidates>,
+    /// Effective-stack threshold in BB below which a preflop (`street == 0`)
+    /// NLH (`format == 2`) decision routes through
+    /// [`crate::pushfold::solve_push_fold`] instead of the equity-bucket
+    /// heuristic. `0.0` (the default) keeps the heuristic path; a typical
+    /// value is `15.0`-`20.0`.
+    #[serde(default)]
+    pub pushfold_threshold_bb: f64,
+}
+
+/// One entry in a villain's range: a hole-card combo plus its relative
+/// weight. Combo length should match the hand's `format` (4/5/6 for PLO,
+/// 2 for NLH).
+#[derive(Debug, Deserialize)]
+#[napi(object)]
+pub struct VillainRangeCombo {
+    pub cards: Vec<u8>,
+    pub weight: f64,
+}
+
+/// Configurable discrete bet sizes for one street, e.g.
+/// `["33%", "75%", "pot", "allin"]`. Percentage/`"pot"` entries are
+/// fractions of the current pot; `"allin"` always resolves to the
+/// remaining stack. See `solver::compute_bet_sizes` for how these combine
+/// with the two thresholds below.
+#[derive(Debug, Deserialize)]
+#[napi(object)]
+pub struct BetSizeCandidates {
+    /// 0 = Preflop, 1 = Flop, 2 = Turn, 3 = River — matches `SolveParams::street`.
+    pub street: u32,
+    pub sizes: Vec<String>,
+    /// Merge the largest configured size into all-in once it's at least
+    /// this fraction of the remaining stack. `0.0` (unset) uses a 0.9
+    /// default.
+    #[serde(default)]
+    pub add_all_in_threshold: f64,
+    /// Replace every configured size with all-in once the smallest one
+    /// would already drop the post-bet SPR below this. `0.0` (unset) uses
+    /// a 1.0 default.
+    #[serde(default)]
+    pub force_all_in_threshold: f64,
+}
+
+/// One (size, frequency) entry in [`SolveResult::bet_sizes`].
+#[derive(Debug, Serialize)]
+#[napi(object)]
+pub struct BetSizeFrequency {
+    pub size_bb100: u32,
+    pub frequency: f64,
 }
 
 /// Output from the solver, sent back to Node.js.
@@ -92,6 +179,10 @@ pub struct SolveResult {
     pub freq_allin: f64,
     /// Confidence in the solution [0.0, 1.0]
     pub confidence: f64,
+    /// Frequency split across `SolveParams::bet_size_candidates` for the
+    /// current street. Empty when no candidates were supplied, in which
+    /// case `raise_amount_bb100` alone describes the sizing.
+    pub bet_sizes: Vec<BetSizeFrequency>,
 }
 
 // ── N-API Exported Functions ────────────────────────────────────────
@@ -170,6 +261,53 @@ pub fn equity(hero_cards: Vec<u8>, board_cards: Vec<u8>, sims: u32) -> Result<f6
     Ok(eq)
 }
 
+/// Same as [`equity`], but scores each sim through the Zobrist-keyed eval
+/// cache (see `evaluator::evaluate_5cards_cached`). Worth it for heavy
+/// multi-opponent PLO6 runs (150 evals/hand) where the same 5-card combos
+/// recur often enough across sims to be worth caching.
+#[napi]
+pub fn equity_cached(hero_cards: Vec<u8>, board_cards: Vec<u8>, sims: u32) -> Result<f64> {
+    let hand_size = hero_cards.len();
+    let eq = omaha::monte_carlo_equity_cached(
+        &hero_cards,
+        &board_cards,
+        &[],
+        sims as usize,
+        1, // 1 opponent
+        hand_size,
+    );
+    Ok(eq)
+}
+
+/// Compute equity against one random opponent using the parallel,
+/// variance-based adaptive Monte Carlo: simulates until the running
+/// standard error drops below `tolerance`, or `max_sims` is reached.
+#[napi]
+pub fn equity_adaptive(
+    hero_cards: Vec<u8>,
+    board_cards: Vec<u8>,
+    tolerance: f64,
+    num_threads: u32,
+    max_sims: u32,
+) -> Result<f64> {
+    let hand_size = hero_cards.len();
+    let params = omaha::AdaptiveEquityParams {
+        tolerance,
+        num_threads: num_threads as usize,
+        max_sims: max_sims as usize,
+        ..Default::default()
+    };
+    let eq = omaha::monte_carlo_equity_adaptive(
+        &hero_cards,
+        &board_cards,
+        &[],
+        1, // 1 opponent
+        hand_size,
+        &params,
+    );
+    Ok(eq)
+}
+
 // ── Internal Helpers ────────────────────────────────────────────────
 
 fn rustc_version() -> &'static str {
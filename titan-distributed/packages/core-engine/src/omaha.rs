@@ -20,6 +20,9 @@ use crate::evaluator;
 
 use rand::prelude::*;
 use rand_xoshiro::Xoshiro256PlusPlus;
+use smallvec::SmallVec;
+
+use std::thread;
 
 // ── Omaha Best-Hand Evaluation ──────────────────────────────────────
 
@@ -58,6 +61,82 @@ pub fn evaluate_omaha(hand: &[u8], board: &[u8]) -> u16 {
     best
 }
 
+/// Same as [`evaluate_omaha`], but routes each 5-card sub-evaluation through
+/// [`evaluator::evaluate_5cards_cached`] instead of `evaluate_5cards`. Worth
+/// it when the same (hole-pair, board-triple) combos recur across many
+/// calls — e.g. [`monte_carlo_equity_cached`]'s thousands of sims — since
+/// repeated combos then cost one cache lookup instead of a full evaluation.
+pub fn evaluate_omaha_cached(hand: &[u8], board: &[u8]) -> u16 {
+    let mut best: u16 = u16::MAX;
+
+    for i in 0..hand.len() {
+        for j in (i + 1)..hand.len() {
+            let h0 = hand[i] as usize;
+            let h1 = hand[j] as usize;
+
+            for a in 0..board.len() {
+                for b in (a + 1)..board.len() {
+                    for c in (b + 1)..board.len() {
+                        let b0 = board[a] as usize;
+                        let b1 = board[b] as usize;
+                        let b2 = board[c] as usize;
+
+                        let rank = evaluator::evaluate_5cards_cached(h0, h1, b0, b1, b2);
+                        if rank < best {
+                            best = rank;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    best
+}
+
+/// Evaluate a Hold'em-style hand: the best 5-card rank from the union of
+/// `hand` and `board`, with no Omaha "exactly 2 from hand" restriction.
+///
+/// When exactly 7 cards are known (the common case: 2 hole + 5 board),
+/// this routes through [`crate::seven_eval::evaluate_7cards`], which scores
+/// all C(7,5) = 21 five-card subsets via the O(1) bitwise evaluator. With
+/// fewer known cards it falls back to brute-force best-of-N-choose-5.
+pub fn evaluate_holdem(hand: &[u8], board: &[u8]) -> u16 {
+    let mut all: Vec<u8> = Vec::with_capacity(hand.len() + board.len());
+    all.extend_from_slice(hand);
+    all.extend_from_slice(board);
+
+    if all.len() == 7 {
+        let mut cards = [0u8; 7];
+        cards.copy_from_slice(&all);
+        return crate::seven_eval::evaluate_7cards(&cards);
+    }
+
+    let mut best: u16 = u16::MAX;
+    let n = all.len();
+    for i in 0..n {
+        for j in (i + 1)..n {
+            for k in (j + 1)..n {
+                for l in (k + 1)..n {
+                    for m in (l + 1)..n {
+                        let rank = evaluator::evaluate_5cards(
+                            all[i] as usize,
+                            all[j] as usize,
+                            all[k] as usize,
+                            all[l] as usize,
+                            all[m] as usize,
+                        );
+                        if rank < best {
+                            best = rank;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
 // ── Monte Carlo Equity ──────────────────────────────────────────────
 
 /// Compute equity via Monte Carlo simulation with Omaha rules.
@@ -79,6 +158,23 @@ pub fn monte_carlo_equity(
     sims: usize,
     opponents: usize,
     hand_size: usize,
+) -> f64 {
+    monte_carlo_equity_seeded(hero_cards, board_cards, dead_cards, sims, opponents, hand_size, 42)
+}
+
+/// Same as [`monte_carlo_equity`], but takes an explicit RNG seed instead of
+/// the fixed `42`. Used by [`crate::cfr`]'s chance-sampling CFR variant,
+/// which needs a *different* board/villain-hand draw on every iteration —
+/// calling `monte_carlo_equity` with `sims = 1` would otherwise replay the
+/// exact same single deal every time.
+pub(crate) fn monte_carlo_equity_seeded(
+    hero_cards: &[u8],
+    board_cards: &[u8],
+    dead_cards: &[u8],
+    sims: usize,
+    opponents: usize,
+    hand_size: usize,
+    seed: u64,
 ) -> f64 {
     // Build deck excluding known cards
     let mut deck: Vec<u8> = Vec::with_capacity(52);
@@ -109,19 +205,567 @@ pub fn monte_carlo_equity(
     }
 
     // Fast RNG (Xoshiro256++ — period 2^256, excellent statistical properties)
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+    let mut wins: u64 = 0;
+    let mut ties: u64 = 0;
+
+    for _ in 0..sims {
+        let score = simulate_one(
+            &mut rng,
+            &mut deck,
+            hero_cards,
+            board_cards,
+            SimShape {
+                board_needed,
+                opponents,
+                hand_size,
+            },
+            evaluate_omaha,
+        );
+
+        if score == 1.0 {
+            wins += 1;
+        } else if score == 0.5 {
+            ties += 1;
+        }
+    }
+
+    let total = sims as f64;
+    (wins as f64 + ties as f64 * 0.5) / total
+}
+
+/// Same as [`monte_carlo_equity`], but scores each trial with
+/// [`evaluate_omaha_cached`]. Worth enabling for heavy multi-opponent PLO6
+/// runs (150 evals/hand — see the combinatorics table above) where the same
+/// 5-card combos recur often enough across sims for the Zobrist cache to
+/// pay for itself; for PLO4/PLO5 or low sim counts the uncached
+/// [`monte_carlo_equity`] is simpler and the hit rate is lower.
+pub fn monte_carlo_equity_cached(
+    hero_cards: &[u8],
+    board_cards: &[u8],
+    dead_cards: &[u8],
+    sims: usize,
+    opponents: usize,
+    hand_size: usize,
+) -> f64 {
+    let mut deck: Vec<u8> = Vec::with_capacity(52);
+    let mut used = [false; 52];
+
+    for &c in hero_cards {
+        used[c as usize] = true;
+    }
+    for &c in board_cards {
+        used[c as usize] = true;
+    }
+    for &c in dead_cards {
+        used[c as usize] = true;
+    }
+
+    for i in 0u8..52 {
+        if !used[i as usize] {
+            deck.push(i);
+        }
+    }
+
+    let board_needed = 5 - board_cards.len();
+    let villain_cards_needed = opponents * hand_size;
+    let total_needed = board_needed + villain_cards_needed;
+
+    if deck.len() < total_needed {
+        return 0.5;
+    }
+
     let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
     let mut wins: u64 = 0;
     let mut ties: u64 = 0;
 
     for _ in 0..sims {
-        // Fisher-Yates partial shuffle (only shuffle what we need)
+        let score = simulate_one(
+            &mut rng,
+            &mut deck,
+            hero_cards,
+            board_cards,
+            SimShape {
+                board_needed,
+                opponents,
+                hand_size,
+            },
+            evaluate_omaha_cached,
+        );
+
+        if score == 1.0 {
+            wins += 1;
+        } else if score == 0.5 {
+            ties += 1;
+        }
+    }
+
+    let total = sims as f64;
+    (wins as f64 + ties as f64 * 0.5) / total
+}
+
+/// Compute Hold'em equity via Monte Carlo simulation, using
+/// [`evaluate_holdem`] instead of the Omaha-constrained evaluator.
+///
+/// Same deal/shuffle path as [`monte_carlo_equity`]; `hand_size` is 2
+/// (hole cards) for every opponent.
+pub fn monte_carlo_equity_holdem(
+    hero_cards: &[u8],
+    board_cards: &[u8],
+    dead_cards: &[u8],
+    sims: usize,
+    opponents: usize,
+) -> f64 {
+    monte_carlo_equity_holdem_seeded(hero_cards, board_cards, dead_cards, sims, opponents, 42)
+}
+
+/// Same as [`monte_carlo_equity_holdem`], but takes an explicit RNG seed
+/// instead of the fixed `42`. Used by [`crate::cfr`]'s NLH chance-sampling
+/// variant, which needs a *different* board/villain-hand draw on every
+/// iteration — calling `monte_carlo_equity_holdem` with `sims = 1` would
+/// otherwise replay the exact same single deal every time.
+pub(crate) fn monte_carlo_equity_holdem_seeded(
+    hero_cards: &[u8],
+    board_cards: &[u8],
+    dead_cards: &[u8],
+    sims: usize,
+    opponents: usize,
+    seed: u64,
+) -> f64 {
+    let hand_size = 2;
+
+    let mut deck: Vec<u8> = Vec::with_capacity(52);
+    let mut used = [false; 52];
+
+    for &c in hero_cards {
+        used[c as usize] = true;
+    }
+    for &c in board_cards {
+        used[c as usize] = true;
+    }
+    for &c in dead_cards {
+        used[c as usize] = true;
+    }
+
+    for i in 0u8..52 {
+        if !used[i as usize] {
+            deck.push(i);
+        }
+    }
+
+    let board_needed = 5 - board_cards.len();
+    let total_needed = board_needed + opponents * hand_size;
+
+    if deck.len() < total_needed {
+        return 0.5;
+    }
+
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+    let mut wins: u64 = 0;
+    let mut ties: u64 = 0;
+
+    for _ in 0..sims {
+        let score = simulate_one(
+            &mut rng,
+            &mut deck,
+            hero_cards,
+            board_cards,
+            SimShape {
+                board_needed,
+                opponents,
+                hand_size,
+            },
+            evaluate_holdem,
+        );
+
+        if score == 1.0 {
+            wins += 1;
+        } else if score == 0.5 {
+            ties += 1;
+        }
+    }
+
+    let total = sims as f64;
+    (wins as f64 + ties as f64 * 0.5) / total
+}
+
+/// Deal shape for a single Monte Carlo trial — how many board cards still
+/// need to be drawn, how many opponents, and how many cards each holds.
+#[derive(Debug, Clone, Copy)]
+struct SimShape {
+    board_needed: usize,
+    opponents: usize,
+    hand_size: usize,
+}
+
+/// Run a single Monte Carlo trial: shuffle the remaining deck, complete the
+/// board, and score hero vs. opponents using `eval_fn`. Returns `1.0`
+/// (win), `0.5` (tie), or `0.0` (loss).
+///
+/// Shared by every `monte_carlo_equity*` variant, parameterized over the
+/// hand evaluator so Omaha and Hold'em reuse the same deal/shuffle path.
+fn simulate_one(
+    rng: &mut Xoshiro256PlusPlus,
+    deck: &mut [u8],
+    hero_cards: &[u8],
+    board_cards: &[u8],
+    shape: SimShape,
+    eval_fn: impl Fn(&[u8], &[u8]) -> u16,
+) -> f64 {
+    let SimShape {
+        board_needed,
+        opponents,
+        hand_size,
+    } = shape;
+    let total_needed = board_needed + opponents * hand_size;
+
+    // Fisher-Yates partial shuffle (only shuffle what we need)
+    let deck_len = deck.len();
+    for k in 0..total_needed.min(deck_len) {
+        let swap_idx = rng.gen_range(k..deck_len);
+        deck.swap(k, swap_idx);
+    }
+
+    // Build complete board
+    let mut full_board = [0u8; 5];
+    for (i, &c) in board_cards.iter().enumerate() {
+        full_board[i] = c;
+    }
+    for i in 0..board_needed {
+        full_board[board_cards.len() + i] = deck[i];
+    }
+
+    // Evaluate hero
+    let hero_rank = eval_fn(hero_cards, &full_board);
+
+    // Evaluate opponents
+    let mut hero_wins = true;
+    let mut is_tie = false;
+    let mut offset = board_needed;
+
+    for _ in 0..opponents {
+        let villain_hand = &deck[offset..offset + hand_size];
+        let villain_rank = eval_fn(villain_hand, &full_board);
+        offset += hand_size;
+
+        if villain_rank < hero_rank {
+            hero_wins = false;
+            is_tie = false;
+            break;
+        } else if villain_rank == hero_rank {
+            is_tie = true;
+        }
+    }
+
+    if hero_wins && !is_tie {
+        1.0
+    } else if is_tie {
+        0.5
+    } else {
+        0.0
+    }
+}
+
+// ── Adaptive Parallel Monte Carlo ────────────────────────────────────
+
+/// Tuning knobs for [`monte_carlo_equity_adaptive`].
+///
+/// `base_seed` is XORed with each worker's thread index so runs are
+/// reproducible but workers don't share a sim stream.
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveEquityParams {
+    /// Stop once the running standard error drops below this.
+    pub tolerance: f64,
+    /// Number of worker threads to split simulations across.
+    pub num_threads: usize,
+    /// XORed with the thread index to seed each worker's RNG.
+    pub base_seed: u64,
+    /// Hard ceiling on total sims across all workers, in case `tolerance`
+    /// is unreachable (e.g. a coin-flip spot).
+    pub max_sims: usize,
+    /// How many sims a worker runs before re-checking convergence.
+    pub batch_size: usize,
+}
+
+impl Default for AdaptiveEquityParams {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.0005,
+            num_threads: 4,
+            base_seed: 42,
+            max_sims: 2_000_000,
+            batch_size: 1000,
+        }
+    }
+}
+
+/// Parallel Monte Carlo equity with variance-based early stopping.
+///
+/// Runs in rounds of `params.num_threads * params.batch_size` sims: each
+/// round spawns one worker per thread, every worker runs exactly
+/// `batch_size` sims with a deterministic per-round seed
+/// (`base_seed ^ thread_id`, mixed with the round index), and the workers'
+/// sums are folded into the running totals only after the whole round has
+/// joined back on the calling thread. The pooled standard error
+/// `SE = sqrt(s²/n)` is then checked there, so convergence never races
+/// against thread scheduling — the same inputs always run the same number
+/// of rounds and see the same sims in the same order. Once `SE` drops below
+/// `tolerance`, or `max_sims` total sims have run, the pooled mean is
+/// returned.
+///
+/// This trades the fixed `sims` count of [`monte_carlo_equity`] for a
+/// reproducible, self-terminating estimate — lopsided spots converge in a
+/// fraction of the sims a close spot needs.
+pub fn monte_carlo_equity_adaptive(
+    hero_cards: &[u8],
+    board_cards: &[u8],
+    dead_cards: &[u8],
+    opponents: usize,
+    hand_size: usize,
+    params: &AdaptiveEquityParams,
+) -> f64 {
+    // Build deck excluding known cards
+    let mut deck: Vec<u8> = Vec::with_capacity(52);
+    let mut used = [false; 52];
+
+    for &c in hero_cards {
+        used[c as usize] = true;
+    }
+    for &c in board_cards {
+        used[c as usize] = true;
+    }
+    for &c in dead_cards {
+        used[c as usize] = true;
+    }
+
+    for i in 0u8..52 {
+        if !used[i as usize] {
+            deck.push(i);
+        }
+    }
+
+    let board_needed = 5 - board_cards.len();
+    let villain_cards_needed = opponents * hand_size;
+    let total_needed = board_needed + villain_cards_needed;
+
+    if deck.len() < total_needed {
+        return 0.5; // Not enough cards for simulation
+    }
+
+    let num_threads = params.num_threads.max(1);
+    let batch_size = params.batch_size.max(1);
+
+    let mut total_sum = 0.0f64;
+    let mut total_sum2 = 0.0f64;
+    let mut total_n: u64 = 0;
+    let mut round: u64 = 0;
+
+    loop {
+        let round_results: Vec<(f64, f64)> = thread::scope(|scope| {
+            let mut handles = Vec::with_capacity(num_threads);
+
+            for thread_id in 0..num_threads {
+                let mut local_deck = deck.clone();
+                // Mix the round index in so repeated rounds don't replay
+                // the same per-thread sim stream.
+                let seed = (params.base_seed ^ thread_id as u64)
+                    .wrapping_add(round.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+
+                handles.push(scope.spawn(move || {
+                    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+                    let mut batch_sum = 0.0f64;
+                    let mut batch_sum2 = 0.0f64;
+
+                    for _ in 0..batch_size {
+                        let x = simulate_one(
+                            &mut rng,
+                            &mut local_deck,
+                            hero_cards,
+                            board_cards,
+                            SimShape {
+                                board_needed,
+                                opponents,
+                                hand_size,
+                            },
+                            evaluate_omaha,
+                        );
+                        batch_sum += x;
+                        batch_sum2 += x * x;
+                    }
+
+                    (batch_sum, batch_sum2)
+                }));
+            }
+
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        for (batch_sum, batch_sum2) in round_results {
+            total_sum += batch_sum;
+            total_sum2 += batch_sum2;
+        }
+        total_n += (num_threads * batch_size) as u64;
+        round += 1;
+
+        let n_f = total_n as f64;
+        let mean = total_sum / n_f;
+        let variance = if n_f > 1.0 {
+            ((total_sum2 - n_f * mean * mean) / (n_f - 1.0)).max(0.0)
+        } else {
+            0.0
+        };
+        let se = (variance / n_f).sqrt();
+
+        if se < params.tolerance || total_n as usize >= params.max_sims {
+            return mean;
+        }
+    }
+}
+
+// ── Range-Aware Monte Carlo ───────────────────────────────────────────
+
+/// A hole-card combo, inline up to PLO6's 6 cards before spilling to the
+/// heap — every sim draws one of these per ranged opponent.
+pub type RangeCombo = SmallVec<[u8; 6]>;
+
+/// A villain's weighted hand range: each entry is a hole-card combo paired
+/// with its relative weight. An empty range means "model this opponent as
+/// fully random", matching the pre-range behavior.
+#[derive(Debug, Clone, Default)]
+pub struct VillainRange {
+    pub combos: Vec<(RangeCombo, f32)>,
+}
+
+impl VillainRange {
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+}
+
+/// How many times to retry sampling a range before giving up and degrading
+/// to a uniform-random hand for that opponent in this sim (can happen when
+/// the board/other villains block most of a tight range).
+const MAX_RANGE_REJECTIONS: u32 = 64;
+
+/// Monte Carlo equity where each opponent carries a weighted range instead
+/// of being dealt a fully random hand.
+///
+/// One combo per ranged opponent is drawn per sim, proportional to weight,
+/// via rejection sampling against cards already in play (board, hero, dead
+/// cards, and any other villain already dealt this sim); a blocked combo is
+/// redrawn. Opponents with an empty range fall back to the original
+/// uniform-random dealing from the shared deck. The final equity is the
+/// weighted average `Σ w·score / Σ w`, where `w` is the product of the
+/// weights of the combos actually dealt to ranged opponents in that sim.
+pub fn monte_carlo_equity_ranged(
+    hero_cards: &[u8],
+    board_cards: &[u8],
+    dead_cards: &[u8],
+    sims: usize,
+    villain_ranges: &[VillainRange],
+    hand_size: usize,
+) -> f64 {
+    monte_carlo_equity_ranged_with(
+        hero_cards,
+        board_cards,
+        dead_cards,
+        sims,
+        villain_ranges,
+        hand_size,
+        evaluate_omaha,
+    )
+}
+
+/// Hold'em variant of [`monte_carlo_equity_ranged`]: same range-aware
+/// dealing, but scored with [`evaluate_holdem`] instead of the
+/// Omaha-constrained evaluator.
+pub fn monte_carlo_equity_ranged_holdem(
+    hero_cards: &[u8],
+    board_cards: &[u8],
+    dead_cards: &[u8],
+    sims: usize,
+    villain_ranges: &[VillainRange],
+) -> f64 {
+    monte_carlo_equity_ranged_with(
+        hero_cards,
+        board_cards,
+        dead_cards,
+        sims,
+        villain_ranges,
+        2,
+        evaluate_holdem,
+    )
+}
+
+fn monte_carlo_equity_ranged_with(
+    hero_cards: &[u8],
+    board_cards: &[u8],
+    dead_cards: &[u8],
+    sims: usize,
+    villain_ranges: &[VillainRange],
+    hand_size: usize,
+    eval_fn: impl Fn(&[u8], &[u8]) -> u16,
+) -> f64 {
+    let mut base_used = [false; 52];
+    for &c in hero_cards {
+        base_used[c as usize] = true;
+    }
+    for &c in board_cards {
+        base_used[c as usize] = true;
+    }
+    for &c in dead_cards {
+        base_used[c as usize] = true;
+    }
+
+    let mut deck: Vec<u8> = (0u8..52).filter(|&c| !base_used[c as usize]).collect();
+
+    let board_needed = 5 - board_cards.len();
+    // Ranged villains draw from their own combo list, not the shared deck;
+    // only uniform (empty-range) villains need deck cards reserved.
+    let uniform_opponents = villain_ranges.iter().filter(|r| r.is_empty()).count();
+    let total_needed = board_needed + uniform_opponents * hand_size;
+
+    if deck.len() < total_needed {
+        return 0.5;
+    }
+
+    // Precompute each range's cumulative weight once, not per sim.
+    let cumulative: Vec<Vec<f32>> = villain_ranges
+        .iter()
+        .map(|range| {
+            let mut running = 0.0f32;
+            range
+                .combos
+                .iter()
+                .map(|&(_, w)| {
+                    running += w;
+                    running
+                })
+                .collect()
+        })
+        .collect();
+    let totals: Vec<f32> = cumulative
+        .iter()
+        .map(|c| c.last().copied().unwrap_or(0.0))
+        .collect();
+
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    let mut win_weight = 0.0f64;
+    let mut total_weight = 0.0f64;
+
+    for _ in 0..sims {
+        // Shuffle the whole deck, not just the `total_needed` window: a
+        // ranged villain's combo can block a card inside that window, in
+        // which case `deal_uniform`'s fallback scan walks into the tail
+        // looking for an unclaimed card — which must be randomized too, or
+        // "uniform" opponents in that (rare) path get deck-order-dependent
+        // cards instead of a random one.
         let deck_len = deck.len();
-        for k in 0..total_needed.min(deck_len) {
+        for k in 0..deck_len {
             let swap_idx = rng.gen_range(k..deck_len);
             deck.swap(k, swap_idx);
         }
 
-        // Build complete board
         let mut full_board = [0u8; 5];
         for (i, &c) in board_cards.iter().enumerate() {
             full_board[i] = c;
@@ -130,19 +774,37 @@ pub fn monte_carlo_equity(
             full_board[board_cards.len() + i] = deck[i];
         }
 
-        // Evaluate hero
-        let hero_rank = evaluate_omaha(hero_cards, &full_board);
+        let mut sim_used = base_used;
+        for i in 0..board_needed {
+            sim_used[deck[i] as usize] = true;
+        }
 
-        // Evaluate opponents
+        let hero_rank = eval_fn(hero_cards, &full_board);
+
+        let mut sim_weight = 1.0f64;
         let mut hero_wins = true;
         let mut is_tie = false;
-        let mut offset = board_needed;
+        let mut uniform_offset = board_needed;
 
-        for _ in 0..opponents {
-            let villain_hand = &deck[offset..offset + hand_size];
-            let villain_rank = evaluate_omaha(villain_hand, &full_board);
-            offset += hand_size;
+        for (range, (range_cumulative, range_total)) in
+            villain_ranges.iter().zip(cumulative.iter().zip(totals.iter()))
+        {
+            let villain_hand = if range.is_empty() {
+                deal_uniform(&deck, &mut uniform_offset, hand_size, &mut sim_used)
+            } else {
+                match sample_weighted_combo(&mut rng, range, range_cumulative, *range_total, &sim_used) {
+                    Some((combo, weight)) => {
+                        for &c in &combo {
+                            sim_used[c as usize] = true;
+                        }
+                        sim_weight *= weight as f64;
+                        combo
+                    }
+                    None => deal_uniform(&deck, &mut uniform_offset, hand_size, &mut sim_used),
+                }
+            };
 
+            let villain_rank = eval_fn(&villain_hand, &full_board);
             if villain_rank < hero_rank {
                 hero_wins = false;
                 is_tie = false;
@@ -153,14 +815,274 @@ pub fn monte_carlo_equity(
         }
 
         if hero_wins && !is_tie {
-            wins += 1;
+            win_weight += sim_weight;
         } else if is_tie {
-            ties += 1;
+            win_weight += 0.5 * sim_weight;
         }
+        total_weight += sim_weight;
     }
 
-    let total = sims as f64;
-    (wins as f64 + ties as f64 * 0.5) / total
+    if total_weight <= 0.0 {
+        return 0.5;
+    }
+    win_weight / total_weight
+}
+
+/// Deal the next `hand_size` unclaimed cards off the shared deck for a
+/// uniform (range-less) opponent. Ranged opponents draw from their own
+/// combo list rather than reserving deck slots, so a card this villain
+/// would otherwise land on may already be marked in `sim_used` — skip
+/// those and keep scanning forward, advancing `offset` past everything
+/// inspected (not just what's dealt) so the next uniform opponent doesn't
+/// reconsider them. Bounded by `deck.len()` since the claimed cards can
+/// push the scan past the window reserved for uniform opponents alone.
+fn deal_uniform(
+    deck: &[u8],
+    offset: &mut usize,
+    hand_size: usize,
+    sim_used: &mut [bool; 52],
+) -> RangeCombo {
+    let mut hand = RangeCombo::new();
+    while hand.len() < hand_size && *offset < deck.len() {
+        let card = deck[*offset];
+        *offset += 1;
+        if sim_used[card as usize] {
+            continue;
+        }
+        sim_used[card as usize] = true;
+        hand.push(card);
+    }
+    hand
+}
+
+/// Sample one combo from `range`, weighted by its stored weight, rejecting
+/// (and redrawing) combos that collide with `used`. Returns `None` if no
+/// unblocked combo is found within [`MAX_RANGE_REJECTIONS`] attempts.
+fn sample_weighted_combo(
+    rng: &mut Xoshiro256PlusPlus,
+    range: &VillainRange,
+    cumulative: &[f32],
+    total_weight: f32,
+    used: &[bool; 52],
+) -> Option<(RangeCombo, f32)> {
+    if total_weight <= 0.0 {
+        return None;
+    }
+
+    for _ in 0..MAX_RANGE_REJECTIONS {
+        let roll = rng.gen_range(0.0..total_weight);
+        let idx = cumulative.partition_point(|&c| c <= roll).min(range.combos.len() - 1);
+        let (combo, weight) = &range.combos[idx];
+
+        if combo.iter().all(|&c| !used[c as usize]) {
+            return Some((combo.clone(), *weight));
+        }
+    }
+
+    None
+}
+
+// ── Exact Enumeration ─────────────────────────────────────────────────
+
+/// Above this many total (board completion × villain hand) combinations,
+/// [`exact_equity`] bails out and lets the caller fall back to sampling —
+/// full enumeration stops being "a few million" and starts being slow.
+const EXACT_ENUMERATION_LIMIT: u64 = 3_000_000;
+
+/// `n choose k`, saturating at `u64::MAX` instead of overflowing.
+fn binomial(n: usize, k: usize) -> u64 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    let mut result: u64 = 1;
+    for i in 0..k {
+        result = result.saturating_mul((n - i) as u64) / (i as u64 + 1);
+    }
+    result
+}
+
+/// All `k`-card combinations of `pool`, as owned `Vec<u8>`s.
+fn all_combinations(pool: &[u8], k: usize) -> Vec<Vec<u8>> {
+    let mut out = Vec::with_capacity(binomial(pool.len(), k) as usize);
+    let mut chosen = Vec::with_capacity(k);
+    combinations_helper(pool, k, 0, &mut chosen, &mut out);
+    out
+}
+
+fn combinations_helper(pool: &[u8], k: usize, start: usize, chosen: &mut Vec<u8>, out: &mut Vec<Vec<u8>>) {
+    if chosen.len() == k {
+        out.push(chosen.clone());
+        return;
+    }
+    for i in start..pool.len() {
+        chosen.push(pool[i]);
+        combinations_helper(pool, k, i + 1, chosen, out);
+        chosen.pop();
+    }
+}
+
+/// Exact win/tie/loss equity by full enumeration — every remaining board
+/// completion crossed with every possible set of opponent holdings,
+/// counted exactly rather than sampled. Returns `None` when the space is
+/// too large (see [`EXACT_ENUMERATION_LIMIT`]); callers should fall back
+/// to [`monte_carlo_equity`] in that case.
+///
+/// The estimate is checked before any enumeration. A 2-card hand heads-up
+/// on the turn (46 × C(45,2) = 45,540) or a PLO5 hand heads-up on the river
+/// (C(42,5) = 850,668) fits under the limit; a PLO5 turn heads-up
+/// (43 × C(42,5) ≈ 36.6M), a PLO6 river heads-up (C(41,6) ≈ 4.5M) and any
+/// earlier street do not, and this returns `None` for them.
+pub fn exact_equity(
+    hero_cards: &[u8],
+    board_cards: &[u8],
+    dead_cards: &[u8],
+    opponents: usize,
+    hand_size: usize,
+) -> Option<f64> {
+    let mut used = [false; 52];
+    for &c in hero_cards {
+        used[c as usize] = true;
+    }
+    for &c in board_cards {
+        used[c as usize] = true;
+    }
+    for &c in dead_cards {
+        used[c as usize] = true;
+    }
+
+    let deck: Vec<u8> = (0u8..52).filter(|&c| !used[c as usize]).collect();
+    let board_needed = 5 - board_cards.len();
+    let total_needed = board_needed + opponents * hand_size;
+
+    if deck.len() < total_needed {
+        return Some(0.5);
+    }
+
+    // Estimate the enumeration size before doing any of it: board
+    // completions × villain hands dealt sequentially from what's left.
+    let board_combo_count = binomial(deck.len(), board_needed);
+    let mut villain_combo_count: u64 = 1;
+    let mut pool_after_board = deck.len() - board_needed;
+    for _ in 0..opponents {
+        villain_combo_count = villain_combo_count.saturating_mul(binomial(pool_after_board, hand_size));
+        pool_after_board = pool_after_board.saturating_sub(hand_size);
+    }
+    let estimated_total = board_combo_count.saturating_mul(villain_combo_count);
+    if estimated_total == 0 || estimated_total > EXACT_ENUMERATION_LIMIT {
+        return None;
+    }
+
+    let board_completions = all_combinations(&deck, board_needed);
+    let villain_hand_combos = all_combinations(&deck, hand_size);
+
+    let mut wins: u64 = 0;
+    let mut ties: u64 = 0;
+    let mut total: u64 = 0;
+
+    for completion in &board_completions {
+        let mut full_board = [0u8; 5];
+        for (i, &c) in board_cards.iter().enumerate() {
+            full_board[i] = c;
+        }
+        for (i, &c) in completion.iter().enumerate() {
+            full_board[board_cards.len() + i] = c;
+        }
+
+        let mut deal_used = used;
+        for &c in completion {
+            deal_used[c as usize] = true;
+        }
+
+        let hero_rank = evaluate_omaha(hero_cards, &full_board);
+
+        enumerate_villains(
+            &villain_hand_combos,
+            opponents,
+            &mut deal_used,
+            &full_board,
+            u16::MAX,
+            hero_rank,
+            &mut wins,
+            &mut ties,
+            &mut total,
+        );
+    }
+
+    let total = total as f64;
+    Some((wins as f64 + ties as f64 * 0.5) / total)
+}
+
+/// Recursively deal one combo per remaining opponent from `combos`
+/// (skipping any that collide with `used`), tracking the best (lowest)
+/// villain rank seen so far. At the leaf, tallies hero win/tie/loss
+/// against that best rank.
+#[allow(clippy::too_many_arguments)]
+fn enumerate_villains(
+    combos: &[Vec<u8>],
+    opponents_left: usize,
+    used: &mut [bool; 52],
+    full_board: &[u8; 5],
+    best_villain_rank: u16,
+    hero_rank: u16,
+    wins: &mut u64,
+    ties: &mut u64,
+    total: &mut u64,
+) {
+    if opponents_left == 0 {
+        *total += 1;
+        if best_villain_rank < hero_rank {
+            // a villain's hand beats hero's — neither a win nor a tie
+        } else if best_villain_rank == hero_rank {
+            *ties += 1;
+        } else {
+            *wins += 1;
+        }
+        return;
+    }
+
+    for combo in combos {
+        if combo.iter().any(|&c| used[c as usize]) {
+            continue;
+        }
+        for &c in combo {
+            used[c as usize] = true;
+        }
+
+        let rank = evaluate_omaha(combo, full_board);
+        enumerate_villains(
+            combos,
+            opponents_left - 1,
+            used,
+            full_board,
+            best_villain_rank.min(rank),
+            hero_rank,
+            wins,
+            ties,
+            total,
+        );
+
+        for &c in combo {
+            used[c as usize] = false;
+        }
+    }
+}
+
+/// Equity with automatic method selection: exact enumeration when the
+/// remaining space is small enough (see [`exact_equity`]), otherwise
+/// Monte Carlo sampling.
+pub fn equity_auto(
+    hero_cards: &[u8],
+    board_cards: &[u8],
+    dead_cards: &[u8],
+    sims: usize,
+    opponents: usize,
+    hand_size: usize,
+) -> f64 {
+    if let Some(exact) = exact_equity(hero_cards, board_cards, dead_cards, opponents, hand_size) {
+        return exact;
+    }
+    monte_carlo_equity(hero_cards, board_cards, dead_cards, sims, opponents, hand_size)
 }
 
 // ── Tests ───────────────────────────────────────────────────────────
@@ -220,4 +1142,213 @@ mod tests {
         assert!(equity > 0.1 && equity < 0.9,
                 "PLO6 equity with 2 villains should be reasonable, got {:.3}", equity);
     }
+
+    #[test]
+    fn test_monte_carlo_adaptive_converges_near_single_threaded() {
+        setup();
+        let hero = vec![48, 49, 40, 36, 32]; // Strong hand
+        let board = vec![50, 44, 38];          // Flop with A♥
+
+        let baseline = monte_carlo_equity(&hero, &board, &[], 50_000, 1, 5);
+        let adaptive = monte_carlo_equity_adaptive(
+            &hero,
+            &board,
+            &[],
+            1,
+            5,
+            &AdaptiveEquityParams {
+                tolerance: 0.003,
+                num_threads: 4,
+                base_seed: 7,
+                max_sims: 500_000,
+                batch_size: 1000,
+            },
+        );
+
+        assert!(
+            (adaptive - baseline).abs() < 0.05,
+            "adaptive equity {:.3} should track baseline {:.3}",
+            adaptive,
+            baseline
+        );
+    }
+
+    #[test]
+    fn test_monte_carlo_adaptive_respects_max_sims() {
+        setup();
+        let hero = vec![48, 49, 40, 36, 32];
+        let board = vec![50, 44, 38];
+
+        // Unreachable tolerance forces the max_sims ceiling to kick in.
+        let equity = monte_carlo_equity_adaptive(
+            &hero,
+            &board,
+            &[],
+            1,
+            5,
+            &AdaptiveEquityParams {
+                tolerance: 0.0,
+                num_threads: 2,
+                base_seed: 7,
+                max_sims: 2000,
+                batch_size: 500,
+            },
+        );
+
+        assert!(equity > 0.0 && equity <= 1.0);
+    }
+
+    #[test]
+    fn test_ranged_empty_range_matches_uniform_behavior() {
+        setup();
+        let hero = vec![48, 49, 40, 36, 32]; // Strong hand
+        let board = vec![50, 44, 38];
+
+        let uniform = monte_carlo_equity(&hero, &board, &[], 2000, 1, 5);
+        let ranged = monte_carlo_equity_ranged(&hero, &board, &[], 2000, &[VillainRange::default()], 5);
+
+        assert!(
+            (uniform - ranged).abs() < 0.05,
+            "empty range should fall back to uniform dealing: {:.3} vs {:.3}",
+            uniform,
+            ranged
+        );
+    }
+
+    #[test]
+    fn test_ranged_narrow_range_changes_equity() {
+        setup();
+        // Board: K♣ J♥ 8♥. Hero holds the other J and 8 for two pair.
+        let hero = vec![39, 27, 4, 13, 28]; // J♠ 8♠ 3♣ 5♦ 9♣ → two pair, Jacks and 8s
+        let board = vec![44, 38, 26]; // K♣ J♥ 8♥
+
+        // Villain's range is pinned to the remaining two Jacks, making trip
+        // Jacks on this board — strictly better than hero's two pair.
+        let villain_range = VillainRange {
+            combos: vec![(SmallVec::from_slice(&[36, 37, 0, 1, 2]), 1.0)], // J♣ J♦ + junk
+        };
+
+        let uniform = monte_carlo_equity(&hero, &board, &[], 3000, 1, 5);
+        let ranged = monte_carlo_equity_ranged(&hero, &board, &[], 3000, &[villain_range], 5);
+
+        assert!(
+            ranged < uniform,
+            "equity vs a locked trip-Jacks range ({:.3}) should be worse than vs random ({:.3})",
+            ranged,
+            uniform
+        );
+    }
+
+    #[test]
+    fn test_deal_uniform_skips_already_used_cards() {
+        let deck = [0u8, 1, 2, 3, 4];
+        let mut offset = 0usize;
+        let mut sim_used = [false; 52];
+        sim_used[0] = true; // claimed by an earlier ranged opponent
+        sim_used[2] = true;
+
+        let hand = deal_uniform(&deck, &mut offset, 2, &mut sim_used);
+
+        assert_eq!(&hand[..], &[1, 3], "should skip already-used deck slots, not reuse them");
+        assert!(sim_used[1] && sim_used[3]);
+    }
+
+    #[test]
+    fn test_ranged_and_uniform_opponents_never_share_a_card() {
+        setup();
+        // Two opponents: the first is pinned to a single combo, the second
+        // is fully random (empty range, dealt off the shared deck). A
+        // uniform deal that doesn't check `sim_used` can land on a card
+        // the ranged opponent already took.
+        let hero = vec![48, 49, 40, 36, 32]; // A♣ A♦ Q♣ J♣ T♣
+        let board = vec![50, 44, 38]; // A♥ K♣ J♥
+
+        let pinned = VillainRange {
+            combos: vec![(SmallVec::from_slice(&[0, 1, 2, 3, 4]), 1.0)],
+        };
+        let ranges = [pinned, VillainRange::default()];
+
+        // Not a property the public API exposes directly, but any
+        // collision would have pushed equity out of [0, 1] or panicked on
+        // an out-of-bounds deck read — run enough sims to make either
+        // failure mode likely if the bug were still present.
+        let equity = monte_carlo_equity_ranged(&hero, &board, &[], 20_000, &ranges, 5);
+        assert!((0.0..=1.0).contains(&equity));
+    }
+
+    #[test]
+    fn test_exact_equity_river_matches_monte_carlo() {
+        setup();
+        // River: full 5-card board, nothing left to sample — exact and
+        // Monte Carlo should agree (within MC noise).
+        let hero = vec![48, 49, 40, 36, 32]; // A♣ A♦ Q♣ J♣ T♣
+        let board = vec![50, 44, 38, 30, 20];
+
+        let exact = exact_equity(&hero, &board, &[], 1, 5).expect("river space is tiny");
+        let mc = monte_carlo_equity(&hero, &board, &[], 20_000, 1, 5);
+
+        assert!(
+            (exact - mc).abs() < 0.03,
+            "exact {:.4} should track Monte Carlo {:.4} on a fixed river",
+            exact,
+            mc
+        );
+    }
+
+    #[test]
+    fn test_exact_equity_turn_is_deterministic() {
+        setup();
+        // Two-card hands keep the turn's (board completion × villain hand)
+        // space small enough to enumerate exactly.
+        let hero = vec![48, 49];
+        let board = vec![50, 44, 38, 30]; // turn: one card left to come
+
+        let first = exact_equity(&hero, &board, &[], 1, 2).expect("turn space is small");
+        let second = exact_equity(&hero, &board, &[], 1, 2).expect("turn space is small");
+
+        assert_eq!(first, second, "exact enumeration has no randomness");
+    }
+
+    #[test]
+    fn test_exact_equity_bails_out_when_space_too_large() {
+        setup();
+        let hero = vec![48, 49, 40, 36, 32];
+        let board = vec![50]; // flop not even complete — far too many completions
+
+        assert!(exact_equity(&hero, &board, &[], 3, 5).is_none());
+    }
+
+    #[test]
+    fn test_equity_auto_prefers_exact_on_river() {
+        setup();
+        let hero = vec![48, 49, 40, 36, 32];
+        let board = vec![50, 44, 38, 30, 20];
+
+        let exact = exact_equity(&hero, &board, &[], 1, 5).unwrap();
+        let auto = equity_auto(&hero, &board, &[], 5000, 1, 5);
+
+        assert_eq!(exact, auto, "equity_auto should pick exact enumeration when available");
+    }
+
+    #[test]
+    fn test_evaluate_omaha_cached_matches_uncached() {
+        setup();
+        let hand = vec![48, 49, 50, 40, 36, 32]; // PLO6: A♣ A♦ A♥ Q♣ J♣ T♣
+        let board = vec![51, 44, 38, 30, 20];
+
+        assert_eq!(evaluate_omaha_cached(&hand, &board), evaluate_omaha(&hand, &board));
+    }
+
+    #[test]
+    fn test_monte_carlo_equity_cached_matches_uncached() {
+        setup();
+        let hero = vec![48, 49, 50, 40, 36, 32]; // PLO6
+        let board = vec![50, 44, 38];
+
+        // Same seed, same deal order, only the evaluator differs — results
+        // should match exactly, not just be close.
+        let uncached = monte_carlo_equity(&hero, &board, &[], 2000, 2, 6);
+        let cached = monte_carlo_equity_cached(&hero, &board, &[], 2000, 2, 6);
+        assert_eq!(uncached, cached, "caching must not change the computed equity");
+    }
 }
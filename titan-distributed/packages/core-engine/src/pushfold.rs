@@ -0,0 +1,282 @@
+//! Titan Core Engine — Push/Fold Nash Equilibrium
+//!
+//! The `spr < 2.0` branch in `solver::compute_strategy` is a crude "commit
+//! or give up" heuristic. For short effective stacks the correct preflop
+//! answer is a solved jam-or-fold equilibrium, which this module computes
+//! by fixed-point iteration over the 169 canonical NLH starting hands:
+//!
+//! 1. Start with a jam range of every hand and a call range of every hand.
+//! 2. The caller's range becomes every hand whose all-in call EV against
+//!    the current jam range is positive (fold EV is normalized to `0.0`).
+//! 3. The jammer's range becomes every hand whose all-in jam EV against
+//!    the current call range is positive (fold EV is again `0.0`).
+//! 4. Repeat until both ranges stop changing, or `MAX_ITERS` is hit.
+//!
+//! All-in EV is scored with [`crate::omaha::monte_carlo_equity_ranged_holdem`]
+//! against the opponent range built so far, so this only models NLH-style
+//! 2-card jams — PLO has no standard compact push/fold range to converge
+//! to, so `solver::solve_state` only routes here when `format == 2`.
+//!
+//! "Multiway" means one jammer against `opponents` callers who all play
+//! the same calling range (no ICM, no divergence between callers) — a
+//! standard simplification, not a full N-way solve.
+
+use crate::omaha::{self, RangeCombo, VillainRange};
+
+const RANKS: [char; 13] = [
+    '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+];
+
+/// One of the 169 canonical NLH starting hands (e.g. "AKs", "76o", "TT").
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HandClass {
+    pub hi: u8,
+    pub lo: u8,
+    /// Ignored when `hi == lo` (a pocket pair has no suited/offsuit split).
+    pub suited: bool,
+}
+
+impl HandClass {
+    pub fn label(&self) -> String {
+        let hi = RANKS[self.hi as usize];
+        let lo = RANKS[self.lo as usize];
+        if self.hi == self.lo {
+            format!("{hi}{lo}")
+        } else if self.suited {
+            format!("{hi}{lo}s")
+        } else {
+            format!("{hi}{lo}o")
+        }
+    }
+
+    /// A concrete 2-card combo standing in for this class — clubs/diamonds
+    /// so a "suited" class is genuinely single-suit and an "offsuit" class
+    /// genuinely isn't.
+    fn representative_combo(&self) -> [u8; 2] {
+        let suit_lo = if self.hi != self.lo && self.suited { 0 } else { 1 };
+        [self.hi << 2, (self.lo << 2) | suit_lo]
+    }
+
+    /// How many of the 1326 raw 2-card combos this class represents.
+    fn combo_count(&self) -> u32 {
+        if self.hi == self.lo {
+            6
+        } else if self.suited {
+            4
+        } else {
+            12
+        }
+    }
+}
+
+/// All 169 canonical starting hands, highest pair first.
+pub fn all_hand_classes() -> Vec<HandClass> {
+    let mut classes = Vec::with_capacity(169);
+    for hi in (0..13u8).rev() {
+        classes.push(HandClass { hi, lo: hi, suited: false });
+        for lo in (0..hi).rev() {
+            classes.push(HandClass { hi, lo, suited: true });
+            classes.push(HandClass { hi, lo, suited: false });
+        }
+    }
+    classes
+}
+
+/// Classify a hero's 2 hole cards into their canonical starting-hand class.
+pub fn classify_hole_cards(cards: &[u8]) -> Option<HandClass> {
+    if cards.len() != 2 {
+        return None;
+    }
+    let r0 = cards[0] >> 2;
+    let r1 = cards[1] >> 2;
+    let (hi, lo) = if r0 >= r1 { (r0, r1) } else { (r1, r0) };
+    let suited = (cards[0] & 3) == (cards[1] & 3);
+    Some(HandClass { hi, lo, suited })
+}
+
+fn range_from_classes(classes: &[HandClass]) -> VillainRange {
+    VillainRange {
+        combos: classes
+            .iter()
+            .map(|c| {
+                (RangeCombo::from_slice(&c.representative_combo()), c.combo_count() as f32)
+            })
+            .collect(),
+    }
+}
+
+// Equity sims per candidate hand per iteration. A push/fold equilibrium
+// only needs to locate the EV=0 jam/call boundary, not a precise equity
+// number — but the boundary hands (the ones this actually matters for)
+// sit close enough to 0 EV that a noisy estimate flips them run-to-run.
+// At 150 sims the standard error on a coin-flip-ish hand is ~0.04, which
+// dwarfs the EV margin most boundary hands are decided by; this needs to
+// be in the low thousands to keep that noise well under the margin.
+const EQUITY_SIMS: usize = 3000;
+const MAX_ITERS: u32 = 8;
+const TOTAL_COMBOS: f64 = 1326.0;
+
+/// All-in call EV for `hand` calling a `stack_bb` jam into a `pot_bb` pot
+/// (blinds/antes already in) against the jammer's range.
+fn call_ev(hand: HandClass, stack_bb: f64, pot_bb: f64, jam_range: &VillainRange) -> f64 {
+    let combo = hand.representative_combo();
+    let equity = omaha::monte_carlo_equity_ranged_holdem(
+        &combo,
+        &[],
+        &[],
+        EQUITY_SIMS,
+        std::slice::from_ref(jam_range),
+    );
+    equity * (pot_bb + stack_bb) - (1.0 - equity) * stack_bb
+}
+
+/// All-in jam EV for `hand` shoving `stack_bb` into a `pot_bb` pot against
+/// `opponents` players who each play `caller_range` and call with
+/// probability `call_prob` (independently estimated from how wide
+/// `caller_range` is).
+fn jam_ev(
+    hand: HandClass,
+    stack_bb: f64,
+    pot_bb: f64,
+    opponents: usize,
+    caller_range: &VillainRange,
+    call_prob: f64,
+) -> f64 {
+    if call_prob <= 0.0 {
+        return pot_bb; // nobody calls, jammer just takes the pot
+    }
+    let combo = hand.representative_combo();
+    let ranges = vec![caller_range.clone(); opponents];
+    let equity = omaha::monte_carlo_equity_ranged_holdem(&combo, &[], &[], EQUITY_SIMS, &ranges);
+    let called_ev = equity * (pot_bb + stack_bb * opponents as f64) - (1.0 - equity) * stack_bb;
+    call_prob * called_ev + (1.0 - call_prob) * pot_bb
+}
+
+/// A solved jam/fold (and call/fold) equilibrium: which canonical hands
+/// jam and which call, for the given effective stack and pot.
+pub struct PushFoldResult {
+    pub jam_range: Vec<HandClass>,
+    pub call_range: Vec<HandClass>,
+}
+
+impl PushFoldResult {
+    /// `1.0` if `hand` is in the jamming range, else `0.0`. This fixed-point
+    /// method resolves each hand to a hard in/out decision rather than a
+    /// mixed frequency — only a hand sitting exactly on the EV=0 boundary
+    /// would need one, and floating-point equity estimates essentially
+    /// never land there.
+    pub fn jam_frequency(&self, hand: HandClass) -> f64 {
+        if self.jam_range.contains(&hand) { 1.0 } else { 0.0 }
+    }
+
+    /// `1.0` if `hand` is in the calling range, else `0.0`. See
+    /// [`PushFoldResult::jam_frequency`] for why this isn't fractional.
+    pub fn call_frequency(&self, hand: HandClass) -> f64 {
+        if self.call_range.contains(&hand) { 1.0 } else { 0.0 }
+    }
+}
+
+/// Solve the jam-or-fold / call-or-fold equilibrium for an effective stack
+/// of `stack_bb`, a preflop pot of `pot_bb` (blinds/antes posted before the
+/// jam), and `opponents` callers behind the jammer.
+pub fn solve_push_fold(stack_bb: f64, pot_bb: f64, opponents: usize) -> PushFoldResult {
+    let classes = all_hand_classes();
+    let mut jam_range = classes.clone();
+    let mut call_range = classes.clone();
+
+    for _ in 0..MAX_ITERS {
+        let jam_villain_range = range_from_classes(&jam_range);
+        let new_call: Vec<HandClass> = classes
+            .iter()
+            .copied()
+            .filter(|&h| call_ev(h, stack_bb, pot_bb, &jam_villain_range) > 0.0)
+            .collect();
+
+        let call_villain_range = range_from_classes(&new_call);
+        let call_combos: f64 = new_call.iter().map(|c| c.combo_count() as f64).sum();
+        let call_prob = 1.0 - (1.0 - call_combos / TOTAL_COMBOS).powi(opponents as i32);
+
+        let new_jam: Vec<HandClass> = classes
+            .iter()
+            .copied()
+            .filter(|&h| {
+                jam_ev(h, stack_bb, pot_bb, opponents, &call_villain_range, call_prob) > 0.0
+            })
+            .collect();
+
+        // Both sides are always filtered from the same fixed-order
+        // `classes` list, so same membership implies same Vec — comparing
+        // lengths alone would miss a same-size range that swapped which
+        // hands are in it.
+        let converged = new_jam == jam_range && new_call == call_range;
+        jam_range = new_jam;
+        call_range = new_call;
+        if converged {
+            break;
+        }
+    }
+
+    PushFoldResult { jam_range, call_range }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluator;
+
+    fn setup() {
+        evaluator::init_tables();
+    }
+
+    #[test]
+    fn test_all_hand_classes_has_169_entries_and_right_combo_counts() {
+        let classes = all_hand_classes();
+        assert_eq!(classes.len(), 169);
+        let total: u32 = classes.iter().map(|c| c.combo_count()).sum();
+        assert_eq!(total, 1326);
+    }
+
+    #[test]
+    fn test_classify_hole_cards_suited_vs_offsuit() {
+        let ace_clubs = 12 << 2;
+        let king_clubs = 11 << 2;
+        let king_diamonds = (11 << 2) | 1;
+        let suited = classify_hole_cards(&[ace_clubs, king_clubs]).unwrap();
+        assert!(suited.suited);
+        assert_eq!(suited.label(), "AKs");
+
+        let offsuit = classify_hole_cards(&[ace_clubs, king_diamonds]).unwrap();
+        assert!(!offsuit.suited);
+        assert_eq!(offsuit.label(), "AKo");
+    }
+
+    #[test]
+    fn test_aa_always_jams_and_calls_at_ten_bb() {
+        setup();
+        let result = solve_push_fold(10.0, 1.5, 1);
+        let aa = HandClass { hi: 12, lo: 12, suited: false };
+        assert_eq!(result.jam_frequency(aa), 1.0);
+        assert_eq!(result.call_frequency(aa), 1.0);
+    }
+
+    #[test]
+    fn test_trash_hand_folds_at_ten_bb() {
+        setup();
+        let result = solve_push_fold(10.0, 1.5, 1);
+        let trash = HandClass { hi: 5, lo: 0, suited: false }; // 72o
+        assert_eq!(result.jam_frequency(trash), 0.0);
+    }
+
+    #[test]
+    fn test_jam_range_shrinks_as_stack_gets_deeper() {
+        setup();
+        let shallow = solve_push_fold(8.0, 1.5, 1);
+        let deep = solve_push_fold(30.0, 1.5, 1);
+        assert!(
+            deep.jam_range.len() < shallow.jam_range.len(),
+            "30bb jam range ({}) should be narrower than 8bb ({})",
+            deep.jam_range.len(),
+            shallow.jam_range.len()
+        );
+    }
+}
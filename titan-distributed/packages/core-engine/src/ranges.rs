@@ -0,0 +1,195 @@
+//! Titan Core Engine — Range Notation Parser
+//!
+//! Parses compact Hold'em-style range notation (e.g. `"66+,A8s+,AJo+,KQs"`)
+//! into weighted [`crate::omaha::VillainRange`] combos. This only covers
+//! 2-card notation — PLO's 4/5/6-card combos have no standard compact
+//! notation, so those are supplied as explicit `VillainRangeCombo` entries
+//! (see `lib.rs`) and never go through this parser.
+//!
+//! ## Supported tokens
+//!
+//! | Token    | Meaning                                      | Combos |
+//! |----------|-----------------------------------------------|--------|
+//! | `66`     | pocket pair                                    | 6      |
+//! | `66+`    | pocket pair and every higher pair (66..AA)     | varies |
+//! | `A8s`    | suited, higher rank first                      | 4      |
+//! | `A8s+`   | suited, kicker from 8 up to one below the top  | varies |
+//! | `AJo`    | offsuit, higher rank first                     | 12     |
+//! | `AJo+`   | offsuit, kicker from J up to one below the top | varies |
+//!
+//! Unrecognized tokens are logged and skipped rather than failing the whole
+//! range — one typo in a 20-token range string shouldn't blank out the rest.
+
+use crate::omaha::RangeCombo;
+
+const RANKS: [char; 13] = [
+    '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+];
+
+fn rank_value(c: char) -> Option<u8> {
+    RANKS
+        .iter()
+        .position(|&r| r == c.to_ascii_uppercase())
+        .map(|i| i as u8)
+}
+
+fn card_id(rank: u8, suit: u8) -> u8 {
+    (rank << 2) | suit
+}
+
+fn pair_combos(rank: u8) -> Vec<(RangeCombo, f32)> {
+    let mut combos = Vec::new();
+    for s1 in 0..4u8 {
+        for s2 in (s1 + 1)..4u8 {
+            combos.push((RangeCombo::from_slice(&[card_id(rank, s1), card_id(rank, s2)]), 1.0));
+        }
+    }
+    combos
+}
+
+fn suited_combos(hi: u8, lo: u8) -> Vec<(RangeCombo, f32)> {
+    (0..4u8)
+        .map(|s| (RangeCombo::from_slice(&[card_id(hi, s), card_id(lo, s)]), 1.0))
+        .collect()
+}
+
+fn offsuit_combos(hi: u8, lo: u8) -> Vec<(RangeCombo, f32)> {
+    let mut combos = Vec::new();
+    for s1 in 0..4u8 {
+        for s2 in 0..4u8 {
+            if s1 != s2 {
+                combos.push((RangeCombo::from_slice(&[card_id(hi, s1), card_id(lo, s2)]), 1.0));
+            }
+        }
+    }
+    combos
+}
+
+/// Expand a single token (e.g. `"A8s+"`) into its matching combos, or
+/// `None` if the token isn't recognized.
+fn expand_token(token: &str) -> Option<Vec<(RangeCombo, f32)>> {
+    let plus = token.ends_with('+');
+    let core = token.strip_suffix('+').unwrap_or(token);
+    let chars: Vec<char> = core.chars().collect();
+
+    match chars.len() {
+        2 => {
+            let r1 = rank_value(chars[0])?;
+            let r2 = rank_value(chars[1])?;
+            if r1 != r2 {
+                return None; // not a pocket pair, e.g. "A8" with no suffix
+            }
+            let top = if plus { 12 } else { r1 };
+            Some((r1..=top).flat_map(pair_combos).collect())
+        }
+        3 => {
+            let hi = rank_value(chars[0])?;
+            let lo = rank_value(chars[1])?;
+            if lo >= hi {
+                return None; // expects the higher rank first, e.g. "A8s"
+            }
+            let suited = match chars[2].to_ascii_lowercase() {
+                's' => true,
+                'o' => false,
+                _ => return None,
+            };
+            let top_lo = if plus { hi - 1 } else { lo };
+            let combos_fn = if suited { suited_combos } else { offsuit_combos };
+            Some((lo..=top_lo).flat_map(|l| combos_fn(hi, l)).collect())
+        }
+        _ => None,
+    }
+}
+
+/// Parse a comma-separated range string into weighted combos, one entry
+/// per matching suit combination, each at weight `1.0`. Blank tokens
+/// (stray commas, surrounding whitespace) are ignored; malformed tokens are
+/// logged and skipped.
+pub fn parse_range_notation(notation: &str) -> Vec<(RangeCombo, f32)> {
+    let mut combos = Vec::new();
+    for raw in notation.split(',') {
+        let token = raw.trim();
+        if token.is_empty() {
+            continue;
+        }
+        match expand_token(token) {
+            Some(expanded) => combos.extend(expanded),
+            None => log::warn!("skipping unrecognized range token {token:?}"),
+        }
+    }
+    combos
+}
+
+/// Drop any combo that shares a card with `blocked` (hero cards, board
+/// cards, known dead cards). Cheaper to do this once up front than to let
+/// every sim rediscover the same blocked combos via rejection sampling.
+pub fn filter_blocked(combos: Vec<(RangeCombo, f32)>, blocked: &[u8]) -> Vec<(RangeCombo, f32)> {
+    combos
+        .into_iter()
+        .filter(|(combo, _)| !combo.iter().any(|c| blocked.contains(c)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pair_token_yields_six_combos() {
+        let combos = parse_range_notation("66");
+        assert_eq!(combos.len(), 6);
+        for (combo, weight) in &combos {
+            assert_eq!(combo.len(), 2);
+            assert_eq!(*weight, 1.0);
+            assert_eq!(combo[0] >> 2, 4); // rank index 4 == '6'
+            assert_eq!(combo[1] >> 2, 4);
+        }
+    }
+
+    #[test]
+    fn test_pair_plus_expands_upward_through_aces() {
+        let combos = parse_range_notation("QQ+");
+        // QQ, KK, AA -> 3 ranks * 6 combos each
+        assert_eq!(combos.len(), 18);
+    }
+
+    #[test]
+    fn test_suited_token_yields_four_combos_same_suit() {
+        let combos = parse_range_notation("KQs");
+        assert_eq!(combos.len(), 4);
+        for (combo, _) in &combos {
+            assert_eq!(combo[0] & 3, combo[1] & 3);
+        }
+    }
+
+    #[test]
+    fn test_offsuit_token_yields_twelve_combos_different_suit() {
+        let combos = parse_range_notation("AJo");
+        assert_eq!(combos.len(), 12);
+        for (combo, _) in &combos {
+            assert_ne!(combo[0] & 3, combo[1] & 3);
+        }
+    }
+
+    #[test]
+    fn test_suited_plus_expands_kicker_up_to_one_below_top() {
+        // A8s+ -> A8s,A9s,ATs,AJs,AQs,AKs = 6 kickers * 4 combos
+        let combos = parse_range_notation("A8s+");
+        assert_eq!(combos.len(), 24);
+    }
+
+    #[test]
+    fn test_multiple_tokens_combine_and_malformed_tokens_are_skipped() {
+        let combos = parse_range_notation("66+,A8s+,AJo+,KQs,??");
+        assert!(!combos.is_empty());
+    }
+
+    #[test]
+    fn test_filter_blocked_removes_combos_sharing_a_card() {
+        let combos = parse_range_notation("AA");
+        let ace_clubs = 12 << 2; // rank A, suit clubs
+        let filtered = filter_blocked(combos, &[ace_clubs]);
+        assert!(filtered.iter().all(|(c, _)| !c.contains(&ace_clubs)));
+        assert_eq!(filtered.len(), 3); // the other 3 AA combos survive
+    }
+}
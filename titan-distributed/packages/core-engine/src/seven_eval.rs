@@ -0,0 +1,116 @@
+//! Titan Core Engine — Seven-Card Evaluator
+//!
+//! Ranks a 7-card hand (2 hole + 5 board) for the NLH path, where
+//! [`crate::omaha::evaluate_holdem`]'s Omaha-style "exactly 2 from hand"
+//! restriction doesn't apply — the best 5 of all 7 cards wins.
+//!
+//! ## Why not a Two-Plus-Two perfect-hash table
+//!
+//! The classic approach precomputes one flat `~32.5M`-entry state-machine
+//! table offline so each of the 7 cards costs one array lookup. An earlier
+//! version of this module built that table lazily behind a global
+//! `Mutex`, growing it row-by-row as new card sets were seen. In practice
+//! that serialized every single evaluation — including the multi-threaded
+//! Monte Carlo hot loop in [`crate::omaha`] — behind one lock, which is
+//! worse than having no shared state at all, and every *first* visit to a
+//! state still paid the full C(7,5) enumeration below anyway.
+//!
+//! [`crate::evaluator::evaluate_5cards`] is already an O(1) bitwise/table
+//! lookup (no combinatorial work, no locking), so C(7,5) = 21 calls to it
+//! is 21 independent O(1) lookups — cheap, trivially safe to call from any
+//! number of threads, and with no shared mutable state to build or guard.
+//! That's what this module does.
+
+use crate::evaluator;
+
+/// Rank a 7-card hand (card IDs 0-51, as everywhere else in this crate).
+/// Returns the same rank space as [`crate::evaluator::evaluate_5cards`]
+/// (1 = best, 7462 = worst): the best of all C(7,5) = 21 five-card subsets.
+pub fn evaluate_7cards(cards: &[u8; 7]) -> u16 {
+    let mut best = u16::MAX;
+    for i in 0..7 {
+        for j in (i + 1)..7 {
+            for k in (j + 1)..7 {
+                for l in (k + 1)..7 {
+                    for m in (l + 1)..7 {
+                        let rank = evaluator::evaluate_5cards(
+                            cards[i] as usize,
+                            cards[j] as usize,
+                            cards[k] as usize,
+                            cards[l] as usize,
+                            cards[m] as usize,
+                        );
+                        if rank < best {
+                            best = rank;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluator;
+
+    fn setup() {
+        evaluator::init_tables();
+    }
+
+    /// `evaluate_7cards` must agree with brute-force best-of-21 for an
+    /// arbitrary hand, regardless of dealing order.
+    #[test]
+    fn test_matches_brute_force_best_of_21() {
+        setup();
+        let cards = [50u8, 44, 38, 30, 20, 48, 49]; // A♥ K♣ J♥ 9♥ 7♣ A♣ A♦
+        let expected = brute_force_best_of_7(&cards);
+
+        assert_eq!(evaluate_7cards(&cards), expected);
+    }
+
+    #[test]
+    fn test_order_independent() {
+        setup();
+        let a = [50u8, 44, 38, 30, 20, 48, 49];
+        let mut b = a;
+        b.reverse();
+
+        assert_eq!(evaluate_7cards(&a), evaluate_7cards(&b));
+    }
+
+    #[test]
+    fn test_royal_flush_among_seven() {
+        setup();
+        // A♠ K♠ Q♠ J♠ T♠ plus two unrelated low cards.
+        let cards = [51u8, 47, 43, 39, 35, 4, 9];
+        assert_eq!(evaluate_7cards(&cards), 1);
+    }
+
+    fn brute_force_best_of_7(cards: &[u8; 7]) -> u16 {
+        let mut best = u16::MAX;
+        for i in 0..7 {
+            for j in (i + 1)..7 {
+                for k in (j + 1)..7 {
+                    for l in (k + 1)..7 {
+                        for m in (l + 1)..7 {
+                            let rank = evaluator::evaluate_5cards(
+                                cards[i] as usize,
+                                cards[j] as usize,
+                                cards[k] as usize,
+                                cards[l] as usize,
+                                cards[m] as usize,
+                            );
+                            if rank < best {
+                                best = rank;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        best
+    }
+}
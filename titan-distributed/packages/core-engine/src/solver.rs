@@ -11,17 +11,35 @@
 //! 4. **Street Scaling**: Aggression increases towards river
 //! 5. **Mixed Strategy**: Output frequency distribution over actions
 //!
+//! ## CFR Solver (opt-in)
+//!
+//! Set `SolveParams::cfr_iterations > 0` to replace step 3's heuristic
+//! with [`crate::cfr::CfrSolver`]: regret-matching CFR over a real (if
+//! scoped-down) betting tree, converging to an equilibrium mixed strategy
+//! instead of fixed equity thresholds. See `cfr.rs` for the algorithm.
+//!
+//! ## Push/Fold Solver (opt-in, preflop NLH only)
+//!
+//! Set `SolveParams::pushfold_threshold_bb` to replace step 3's heuristic
+//! with [`crate::pushfold::solve_push_fold`] whenever the effective stack
+//! is below that threshold on `street == 0` for NLH (`format == 2`): a
+//! solved jam/fold equilibrium instead of the `spr < 2.0` commit-or-give-up
+//! heuristic. See `pushfold.rs` for the algorithm.
+//!
 //! ## Future: Deep CFR Integration
 //!
-//! In production, steps 1-5 will be replaced by a Deep CFR neural network
-//! lookup. The network is trained offline on billions of Omaha game trees
-//! using Counterfactual Regret Minimization. The lookup is O(1) — just
-//! a forward pass through the network (~0.3ms).
+//! In production, the tabular CFR solver above would be replaced by a Deep
+//! CFR neural network lookup. The network is trained offline on billions of
+//! Omaha game trees using Counterfactual Regret Minimization. The lookup is
+//! O(1) — just a forward pass through the network (~0.3ms).
 
 use crate::SolveParams;
 use crate::SolveResult;
 use crate::omaha;
 use crate::evaluator;
+use crate::cfr;
+use crate::pushfold;
+use crate::ranges;
 
 /// Main solver entry point. Called from N-API `solve()`.
 pub fn solve_state(params: &SolveParams) -> SolveResult {
@@ -30,6 +48,7 @@ pub fn solve_state(params: &SolveParams) -> SolveResult {
     let hand_size = match params.format {
         0 => 5, // PLO5
         1 => 6, // PLO6
+        2 => 2, // NLH
         _ => 5,
     };
 
@@ -44,24 +63,174 @@ pub fn solve_state(params: &SolveParams) -> SolveResult {
     let opponents = (params.num_players.saturating_sub(1)).max(1) as usize;
 
     // ── Step 1: Compute Equity ──────────────────────────────────────
-    let equity = omaha::monte_carlo_equity(
-        &params.hero_cards,
-        &params.board_cards,
-        &params.dead_cards,
-        sims,
-        opponents,
-        hand_size,
-    );
+    // Per-opponent weighted ranges, if the caller supplied any; opponents
+    // past the end of `villain_ranges`/`villain_range_notation` (or with
+    // nothing in either) are dealt fully random hands, same as before
+    // ranges existed. Explicit combos and parsed notation for the same
+    // opponent are merged into one range.
+    let blocked: Vec<u8> = params
+        .hero_cards
+        .iter()
+        .chain(params.board_cards.iter())
+        .chain(params.dead_cards.iter())
+        .copied()
+        .collect();
+    let villain_ranges: Vec<omaha::VillainRange> = (0..opponents)
+        .map(|i| {
+            let mut combos: Vec<(omaha::RangeCombo, f32)> = params
+                .villain_ranges
+                .get(i)
+                .map(|cs| {
+                    cs.iter()
+                        .map(|c| (c.cards.iter().copied().collect(), c.weight as f32))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if let Some(notation) = params.villain_range_notation.get(i) {
+                combos.extend(ranges::parse_range_notation(notation));
+            }
+            omaha::VillainRange {
+                combos: ranges::filter_blocked(combos, &blocked),
+            }
+        })
+        .collect();
+    let has_ranges = villain_ranges.iter().any(|r| !r.is_empty());
+
+    // NLH ranks best-of-7 (no Omaha "exactly 2 from hand" rule), so it
+    // routes through the seven-card evaluator instead.
+    let equity = match (params.format == 2, has_ranges) {
+        (true, true) => omaha::monte_carlo_equity_ranged_holdem(
+            &params.hero_cards,
+            &params.board_cards,
+            &params.dead_cards,
+            sims,
+            &villain_ranges,
+        ),
+        (true, false) => omaha::monte_carlo_equity_holdem(
+            &params.hero_cards,
+            &params.board_cards,
+            &params.dead_cards,
+            sims,
+            opponents,
+        ),
+        (false, true) => omaha::monte_carlo_equity_ranged(
+            &params.hero_cards,
+            &params.board_cards,
+            &params.dead_cards,
+            sims,
+            &villain_ranges,
+            hand_size,
+        ),
+        // No ranges: prefer exact enumeration when the remaining space is
+        // under `exact_equity`'s limit (for PLO5/PLO6 that is only a PLO5
+        // river heads-up), falling back to Monte Carlo otherwise.
+        (false, false) => omaha::equity_auto(
+            &params.hero_cards,
+            &params.board_cards,
+            &params.dead_cards,
+            sims,
+            opponents,
+            hand_size,
+        ),
+    };
 
     // ── Step 2: SPR Analysis ────────────────────────────────────────
     let pot = params.pot_bb100.max(1) as f64;
     let stack = params.hero_stack as f64;
     let spr = stack / pot;
+    let villain_stack = params.villain_stacks.first().copied().unwrap_or(params.hero_stack);
 
     // ── Step 3: Strategy Computation ────────────────────────────────
-    let (action, frequencies, raise_amount) = compute_strategy(
-        equity, spr, params.street, params.position, opponents,
-    );
+    let effective_stack_bb = stack.min(villain_stack as f64) / 100.0;
+    let is_short_stack_nlh_preflop = params.format == 2
+        && params.street == 0
+        && params.pushfold_threshold_bb > 0.0
+        && effective_stack_bb < params.pushfold_threshold_bb;
+
+    let (action, frequencies, raise_amount) = if is_short_stack_nlh_preflop {
+        // Short-stack NLH preflop: a solved jam/fold equilibrium beats the
+        // equity-bucket heuristic below. The BB is the only seat that can
+        // already be facing a shove heads-up, so it reads the call side of
+        // the equilibrium; every other position reads the jam side, mirroring
+        // the commit-or-give-up shape of the heuristic's own `spr < 2.0`
+        // branch.
+        let pot_bb = pot / 100.0;
+        let result = pushfold::solve_push_fold(effective_stack_bb, pot_bb, opponents);
+        let hand = pushfold::classify_hole_cards(&params.hero_cards);
+        let is_bb_facing_shove = params.position == 2 && opponents == 1;
+        if is_bb_facing_shove {
+            let calls = hand.map(|h| result.call_frequency(h) > 0.0).unwrap_or(false);
+            log::debug!(
+                "pushfold: BB defend with {} -> call={calls}",
+                hand.map(|h| h.label()).unwrap_or_else(|| "??".to_string())
+            );
+            if calls {
+                (2, [0.0, 0.0, 1.0, 0.0, 0.0], 0)
+            } else {
+                (0, [1.0, 0.0, 0.0, 0.0, 0.0], 0)
+            }
+        } else {
+            let jams = hand.map(|h| result.jam_frequency(h) > 0.0).unwrap_or(false);
+            log::debug!(
+                "pushfold: open with {} -> jam={jams}",
+                hand.map(|h| h.label()).unwrap_or_else(|| "??".to_string())
+            );
+            if jams {
+                (4, [0.0, 0.0, 0.0, 0.0, 1.0], 0)
+            } else {
+                (0, [1.0, 0.0, 0.0, 0.0, 0.0], 0)
+            }
+        }
+    } else if params.cfr_iterations > 0 {
+        // Under 500 iterations use vanilla CFR, whose terminals reuse one
+        // multi-thousand-sim equity estimate every iteration; from 500 on
+        // use CFR-CS, which scores each iteration with one freshly sampled
+        // runout instead.
+        let variant = if params.cfr_iterations < 500 {
+            cfr::Variant::Vanilla
+        } else {
+            cfr::Variant::ChanceSampled
+        };
+        let mut solver = cfr::CfrSolver::new();
+        let freq = solver.solve(
+            &params.hero_cards,
+            &params.board_cards,
+            &params.dead_cards,
+            params.pot_bb100,
+            params.hero_stack,
+            villain_stack,
+            opponents,
+            hand_size,
+            params.cfr_iterations,
+            variant,
+        );
+        let action = freq
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .map(|(i, _)| i as u32)
+            .unwrap_or(0);
+        let raise_amount = if freq[3] > 0.0 || freq[4] > 0.0 {
+            cfr::CfrSolver::opening_bet_bb100(params.pot_bb100, params.hero_stack, villain_stack)
+        } else {
+            0
+        };
+        (action, freq, raise_amount)
+    } else {
+        compute_strategy(equity, spr, params.street, params.position, opponents)
+    };
+
+    // ── Step 3b: Bet Sizing (heuristic path only) ───────────────────
+    let bet_sizes = if params.cfr_iterations == 0 && !is_short_stack_nlh_preflop {
+        params
+            .bet_size_candidates
+            .iter()
+            .find(|c| c.street == params.street)
+            .map(|c| compute_bet_sizes(c, equity, pot, stack, frequencies[3], frequencies[4]))
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
 
     // ── Step 4: EV Estimation ───────────────────────────────────────
     let ev_bb100 = compute_ev(equity, pot, &frequencies, raise_amount as f64);
@@ -80,6 +249,7 @@ pub fn solve_state(params: &SolveParams) -> SolveResult {
         freq_call:  frequencies[2],
         freq_raise: frequencies[3],
         freq_allin: frequencies[4],
+        bet_sizes,
         confidence,
     }
 }
@@ -212,6 +382,112 @@ fn compute_strategy(
     (action, freq, raise_amount)
 }
 
+/// A parsed [`crate::BetSizeCandidates`] token, still in fraction-of-pot
+/// terms — `compute_bet_sizes` resolves it to a bb100 amount.
+enum BetSizeToken {
+    PotFraction(f64),
+    AllIn,
+}
+
+fn parse_bet_size_token(raw: &str) -> Option<BetSizeToken> {
+    let s = raw.trim();
+    if s.eq_ignore_ascii_case("allin") || s.eq_ignore_ascii_case("all-in") {
+        return Some(BetSizeToken::AllIn);
+    }
+    if s.eq_ignore_ascii_case("pot") {
+        return Some(BetSizeToken::PotFraction(1.0));
+    }
+    let pct: f64 = s.strip_suffix('%')?.parse().ok()?;
+    Some(BetSizeToken::PotFraction(pct / 100.0))
+}
+
+/// Split the combined raise+allin frequency across `candidates.sizes`.
+///
+/// Weighting favors the largest sizes as equity climbs toward the premium
+/// end (polarized value bets go big) and the smallest sizes through the
+/// medium bands (thinner value / blocking bets) — the same equity-bracket
+/// shape `compute_strategy` already uses, just spread across sizes instead
+/// of collapsed into one.
+///
+/// The largest resolved size merges into all-in once it's within
+/// `add_all_in_threshold` of the remaining stack; every size collapses
+/// into all-in if the smallest one would already drop the post-bet SPR
+/// below `force_all_in_threshold`.
+fn compute_bet_sizes(
+    candidates: &crate::BetSizeCandidates,
+    equity: f64,
+    pot: f64,
+    stack: f64,
+    raise_freq: f64,
+    allin_freq: f64,
+) -> Vec<crate::BetSizeFrequency> {
+    let combined_freq = raise_freq + allin_freq;
+    if combined_freq <= 0.0 {
+        return Vec::new();
+    }
+
+    let add_all_in_threshold = if candidates.add_all_in_threshold > 0.0 {
+        candidates.add_all_in_threshold
+    } else {
+        0.9
+    };
+    let force_all_in_threshold = if candidates.force_all_in_threshold > 0.0 {
+        candidates.force_all_in_threshold
+    } else {
+        1.0
+    };
+
+    let mut sizes_bb100: Vec<u32> = candidates
+        .sizes
+        .iter()
+        .filter_map(|s| parse_bet_size_token(s))
+        .map(|token| match token {
+            BetSizeToken::AllIn => stack as u32,
+            BetSizeToken::PotFraction(frac) => ((pot * frac) as u32).min(stack as u32),
+        })
+        .collect();
+    sizes_bb100.sort_unstable();
+    sizes_bb100.dedup();
+
+    if sizes_bb100.is_empty() {
+        return Vec::new();
+    }
+
+    let smallest = sizes_bb100[0] as f64;
+    let remaining_after_smallest = (stack - smallest).max(0.0);
+    if remaining_after_smallest / pot.max(1.0) < force_all_in_threshold {
+        return vec![crate::BetSizeFrequency {
+            size_bb100: stack as u32,
+            frequency: combined_freq,
+        }];
+    }
+
+    if let Some(last) = sizes_bb100.last_mut() {
+        if *last as f64 >= stack * add_all_in_threshold {
+            *last = stack as u32;
+        }
+    }
+    sizes_bb100.dedup();
+
+    let n = sizes_bb100.len();
+    let weights: Vec<f64> = (0..n)
+        .map(|i| {
+            let rank = i as f64 / (n.max(2) - 1) as f64; // 0 (smallest) .. 1 (largest)
+            if equity > 0.6 { 0.1 + rank } else { 1.1 - rank }
+        })
+        .collect();
+    let weight_sum: f64 = weights.iter().sum();
+
+    sizes_bb100
+        .into_iter()
+        .zip(weights)
+        .map(|(size_bb100, w)| crate::BetSizeFrequency {
+            size_bb100,
+            frequency: combined_freq * (w / weight_sum),
+        })
+        .collect()
+}
+
 /// Estimate expected value in BB×100.
 fn compute_ev(equity: f64, pot: f64, frequencies: &[f64; 5], raise: f64) -> i32 {
     // Simplified EV:
@@ -279,4 +555,65 @@ mod tests {
         assert!(freq[0] > 0.7, "Low SPR + bad equity → should fold");
         assert_eq!(action, 0, "Should fold");
     }
+
+    fn bet_size_candidates(sizes: &[&str]) -> crate::BetSizeCandidates {
+        crate::BetSizeCandidates {
+            street: 1,
+            sizes: sizes.iter().map(|s| s.to_string()).collect(),
+            add_all_in_threshold: 0.0,
+            force_all_in_threshold: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_bet_sizes_frequencies_sum_to_combined_frequency() {
+        let candidates = bet_size_candidates(&["33%", "75%", "pot"]);
+        let sizes = compute_bet_sizes(&candidates, 0.7, 10000.0, 50000.0, 0.5, 0.1);
+        let total: f64 = sizes.iter().map(|s| s.frequency).sum();
+        assert!((total - 0.6).abs() < 1e-9, "expected 0.6, got {total}");
+    }
+
+    #[test]
+    fn test_bet_sizes_empty_when_no_raise_or_allin_frequency() {
+        let candidates = bet_size_candidates(&["33%", "pot"]);
+        let sizes = compute_bet_sizes(&candidates, 0.5, 10000.0, 50000.0, 0.0, 0.0);
+        assert!(sizes.is_empty());
+    }
+
+    #[test]
+    fn test_bet_sizes_largest_merges_into_allin_near_stack() {
+        let candidates = crate::BetSizeCandidates {
+            street: 1,
+            sizes: vec!["90%".to_string()],
+            add_all_in_threshold: 0.8,
+            // Low enough that the post-bet SPR of 0.1 (1000 left / 10000 pot)
+            // doesn't trigger the force-all-in rule (the 1.0 default would).
+            force_all_in_threshold: 0.01,
+        };
+        // 90% of a 10000 pot is 9000, which is >= 80% of a 10000 stack.
+        let sizes = compute_bet_sizes(&candidates, 0.8, 10000.0, 10000.0, 0.5, 0.0);
+        assert_eq!(sizes.len(), 1);
+        assert_eq!(sizes[0].size_bb100, 10000);
+    }
+
+    #[test]
+    fn test_bet_sizes_force_allin_when_post_bet_spr_too_low() {
+        let candidates = crate::BetSizeCandidates {
+            street: 1,
+            sizes: vec!["33%".to_string()],
+            add_all_in_threshold: 0.0,
+            force_all_in_threshold: 5.0, // unreasonably high, always forces
+        };
+        let sizes = compute_bet_sizes(&candidates, 0.5, 10000.0, 50000.0, 0.5, 0.0);
+        assert_eq!(sizes.len(), 1);
+        assert_eq!(sizes[0].size_bb100, 50000);
+        assert!((sizes[0].frequency - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bet_sizes_high_equity_favors_largest_size() {
+        let candidates = bet_size_candidates(&["33%", "pot"]);
+        let sizes = compute_bet_sizes(&candidates, 0.9, 10000.0, 50000.0, 0.6, 0.0);
+        assert!(sizes[1].frequency > sizes[0].frequency, "pot-size bet should get more weight at high equity");
+    }
 }